@@ -7,7 +7,7 @@ use rand::distributions::Uniform;
 use rand::Rng;
 
 extern crate indexlist;
-use indexlist::IndexList;
+use indexlist::{FreeStrategy, IndexList};
 
 extern crate generational_arena;
 use generational_arena::Arena;
@@ -122,7 +122,109 @@ fn criterion_benchmark(c: &mut Criterion) {
 
     // no input
     c.bench_functions("pop_front8", functions, 0);
+
+    let index_list = Fun::new("index_list", move |b, _| {
+        b.iter_with_setup(
+            || {
+                let mut index_list = IndexList::new();
+
+                let mut indexes: Vec<_> = (0..1_000).map(|i| index_list.push_back(i)).collect();
+
+                rand::thread_rng().shuffle(&mut indexes);
+
+                (index_list, indexes)
+            },
+            |(mut index_list, indexes)| {
+                for index in indexes {
+                    index_list.remove(index);
+                }
+            },
+        )
+    });
+
+    let functions = vec![index_list];
+
+    // no input
+    c.bench_functions("remove_random", functions, 0);
+
+    // churn: free a random half of the slots, refill them, and then walk the
+    // list, which shows how each strategy's reuse order affects locality
+    let churn = |strategy| {
+        move |b: &mut criterion::Bencher, _: &usize| {
+            b.iter_with_setup(
+                || {
+                    let mut index_list = IndexList::new().with_free_strategy(strategy);
+
+                    let mut indexes: Vec<_> =
+                        (0..10_000).map(|i| index_list.push_back(i)).collect();
+
+                    rand::thread_rng().shuffle(&mut indexes);
+
+                    for &index in &indexes[..5_000] {
+                        index_list.remove(index);
+                    }
+
+                    index_list
+                },
+                |mut index_list| {
+                    for i in 0..5_000 {
+                        index_list.push_back(i);
+                    }
+
+                    index_list.iter().sum::<i32>()
+                },
+            )
+        }
+    };
+
+    let lifo = Fun::new("lifo", churn(FreeStrategy::Lifo));
+    let fifo = Fun::new("fifo", churn(FreeStrategy::Fifo));
+
+    let functions = vec![lifo, fifo];
+
+    // no input
+    c.bench_functions("free_strategy", functions, 0);
+
+    indexed_benchmark(c);
 }
 
+#[cfg(feature = "indexed")]
+fn indexed_benchmark(c: &mut Criterion) {
+    use indexlist::IndexedList;
+
+    let iterations = 100_000;
+
+    let mut index_list = IndexList::new();
+    let mut indexed_list = IndexedList::new();
+
+    let mut rng = rand::thread_rng();
+    let range = Uniform::new_inclusive(0, iterations);
+    let mut numbers = rng.sample_iter(&range);
+
+    for _ in 0..iterations {
+        let number = numbers.next().unwrap();
+        index_list.push_back(number);
+        indexed_list.push_back(number);
+    }
+
+    let needle = numbers.next().unwrap();
+
+    let index_list = Fun::new("index_list", move |b, _| {
+        b.iter(|| index_list.index_of(&needle))
+    });
+
+    let indexed_list = Fun::new("indexed_list", move |b, _| {
+        b.iter(|| indexed_list.index_of(&needle))
+    });
+
+    let functions = vec![index_list, indexed_list];
+
+    // no input
+    c.bench_functions("index_of", functions, 0);
+}
+
+#[cfg(not(feature = "indexed"))]
+fn indexed_benchmark(_: &mut Criterion) {}
+
 criterion_group!(benches, criterion_benchmark);
 criterion_main!(benches);
@@ -0,0 +1,355 @@
+//! An `IndexList<T>` that can find its items quickly.
+//!
+//! This module is only available with the `indexed` feature.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use {Index, IndexList};
+
+/// A doubly linked list, backed by a vector, that also keeps a map from each
+/// item to the indexes it's stored at.
+///
+/// `IndexList<T>::index_of` and `contains` have to walk the whole list. This
+/// type keeps a `HashMap` alongside the list, so those are constant time
+/// instead. In exchange, every insertion stores a clone of the item as a key,
+/// and every insertion and removal has to update the map.
+///
+/// Because the map is keyed on the items, you can't get a mutable reference
+/// to an item; changing it would leave the map out of date. Remove it and
+/// insert a new one instead.
+///
+/// # Examples
+///
+/// Finding an item:
+///
+/// ```
+/// use indexlist::IndexedList;
+///
+/// let mut list = IndexedList::new();
+///
+/// list.push_back(5);
+/// let ten = list.push_back(10);
+///
+/// assert_eq!(list.index_of(&10), Some(ten));
+/// assert!(!list.contains(&15));
+/// ```
+#[derive(Debug)]
+pub struct IndexedList<T>
+where
+    T: PartialEq + Eq + Hash + Clone + std::fmt::Debug,
+{
+    list: IndexList<T>,
+    positions: HashMap<T, Vec<Index<T>>>,
+}
+
+impl<T> Default for IndexedList<T>
+where
+    T: PartialEq + Eq + Hash + Clone + std::fmt::Debug,
+{
+    fn default() -> Self {
+        IndexedList {
+            list: IndexList::new(),
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<T> IndexedList<T>
+where
+    T: PartialEq + Eq + Hash + Clone + std::fmt::Debug,
+{
+    /// Creates a new, empty `IndexedList<T>`.
+    ///
+    /// # Examples
+    ///
+    /// Making a new list:
+    ///
+    /// ```
+    /// use indexlist::IndexedList;
+    ///
+    /// let list: IndexedList<i32> = IndexedList::new();
+    ///
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn new() -> IndexedList<T> {
+        IndexedList::default()
+    }
+
+    /// Returns the number of items in the list.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the list has no items in it.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns a reference to the underlying `IndexList<T>`.
+    ///
+    /// # Examples
+    ///
+    /// Using the list's own methods:
+    ///
+    /// ```
+    /// use indexlist::IndexedList;
+    ///
+    /// let mut list = IndexedList::new();
+    ///
+    /// list.push_back(5);
+    ///
+    /// assert_eq!(list.as_list().head(), Some(&5));
+    /// ```
+    pub fn as_list(&self) -> &IndexList<T> {
+        &self.list
+    }
+
+    /// Returns the underlying `IndexList<T>`, throwing away the map.
+    ///
+    /// Any indexes you hold are still valid in the returned list.
+    pub fn into_list(self) -> IndexList<T> {
+        self.list
+    }
+
+    /// Adds this item to the tail of the list.
+    pub fn push_back(&mut self, item: T) -> Index<T> {
+        let index = self.list.push_back(item.clone());
+        self.track(item, index);
+
+        index
+    }
+
+    /// Adds this item to the head of the list.
+    pub fn push_front(&mut self, item: T) -> Index<T> {
+        let index = self.list.push_front(item.clone());
+        self.track(item, index);
+
+        index
+    }
+
+    /// Inserts an item immediately before the provided index.
+    ///
+    /// Returns `None` if the index isn't valid.
+    pub fn insert_before(&mut self, index: Index<T>, item: T) -> Option<Index<T>> {
+        let index = self.list.insert_before(index, item.clone())?;
+        self.track(item, index);
+
+        Some(index)
+    }
+
+    /// Inserts an item immediately after the provided index.
+    ///
+    /// Returns `None` if the index isn't valid.
+    pub fn insert_after(&mut self, index: Index<T>, item: T) -> Option<Index<T>> {
+        let index = self.list.insert_after(index, item.clone())?;
+        self.track(item, index);
+
+        Some(index)
+    }
+
+    /// Removes the item at this index, and returns it.
+    ///
+    /// Returns `None` if the index isn't valid.
+    ///
+    /// # Examples
+    ///
+    /// Removing an item means you can't find it any more:
+    ///
+    /// ```
+    /// use indexlist::IndexedList;
+    ///
+    /// let mut list = IndexedList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// assert_eq!(list.remove(five), Some(5));
+    /// assert!(!list.contains(&5));
+    /// ```
+    pub fn remove(&mut self, index: Index<T>) -> Option<T> {
+        let item = self.list.remove(index)?;
+        self.untrack(&item, index);
+
+        Some(item)
+    }
+
+    /// Removes the head of the list, and returns it.
+    ///
+    /// Returns `None` if the list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let index = self.list.head_index()?;
+
+        self.remove(index)
+    }
+
+    /// Removes the tail of the list, and returns it.
+    ///
+    /// Returns `None` if the list is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let index = self.list.tail_index()?;
+
+        self.remove(index)
+    }
+
+    /// Returns a reference to the item at this index, if it exists.
+    pub fn get(&self, index: Index<T>) -> Option<&T> {
+        self.list.get(index)
+    }
+
+    /// Returns an iterator of references to the items in the list.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.list.iter()
+    }
+
+    /// Returns `true` if this item is in the list.
+    ///
+    /// Unlike `IndexList<T>::contains`, this doesn't need to walk the list.
+    pub fn contains(&self, item: &T) -> bool {
+        self.positions.contains_key(item)
+    }
+
+    /// Returns an index to this item, if it's in the list.
+    ///
+    /// Unlike `IndexList<T>::index_of`, this doesn't need to walk the list.
+    /// That also means that if the item is in the list more than once, you get
+    /// the index of whichever copy was inserted first, which isn't always the
+    /// one closest to the head.
+    ///
+    /// # Examples
+    ///
+    /// Finding an item:
+    ///
+    /// ```
+    /// use indexlist::IndexedList;
+    ///
+    /// let mut list = IndexedList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// assert_eq!(list.index_of(&5), Some(five));
+    /// assert_eq!(list.index_of(&10), None);
+    /// ```
+    pub fn index_of(&self, item: &T) -> Option<Index<T>> {
+        self.positions
+            .get(item)
+            .and_then(|indexes| indexes.first().cloned())
+    }
+
+    // records that this item is stored at this index
+    fn track(&mut self, item: T, index: Index<T>) {
+        self.positions.entry(item).or_default().push(index);
+    }
+
+    // forgets that this item was stored at this index
+    fn untrack(&mut self, item: &T, index: Index<T>) {
+        let now_empty = match self.positions.get_mut(item) {
+            Some(indexes) => {
+                indexes.retain(|&i| i != index);
+                indexes.is_empty()
+            }
+            None => panic!("IndexedList: map out of sync with list"),
+        };
+
+        if now_empty {
+            self.positions.remove(item);
+        }
+    }
+}
+
+impl<T> From<IndexList<T>> for IndexedList<T>
+where
+    T: PartialEq + Eq + Hash + Clone + std::fmt::Debug,
+{
+    fn from(list: IndexList<T>) -> IndexedList<T> {
+        let mut positions: HashMap<T, Vec<Index<T>>> = HashMap::new();
+        let mut next = list.head_index();
+
+        while let Some(index) = next {
+            positions
+                .entry(list[index].clone())
+                .or_default()
+                .push(index);
+            next = list.next_index(index);
+        }
+
+        IndexedList { list, positions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_find() {
+        let mut list = IndexedList::new();
+
+        let five = list.push_back(5);
+        let ten = list.push_back(10);
+        let zero = list.push_front(0);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.index_of(&5), Some(five));
+        assert_eq!(list.index_of(&10), Some(ten));
+        assert_eq!(list.index_of(&0), Some(zero));
+        assert_eq!(list.index_of(&1), None);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [0, 5, 10]);
+    }
+
+    #[test]
+    fn remove_keeps_map_in_sync() {
+        let mut list = IndexedList::new();
+
+        let first = list.push_back(5);
+        let second = list.push_back(5);
+        list.push_back(10);
+
+        assert_eq!(list.remove(first), Some(5));
+        assert_eq!(list.index_of(&5), Some(second));
+
+        assert_eq!(list.remove(second), Some(5));
+        assert!(!list.contains(&5));
+        assert_eq!(list.remove(second), None);
+
+        // the freed slot gets reused, and the map follows along
+        let six = list.push_back(6);
+
+        assert_eq!(list.index_of(&6), Some(six));
+        assert_eq!(list.pop_back(), Some(6));
+        assert_eq!(list.pop_front(), Some(10));
+        assert!(list.is_empty());
+        assert!(list.positions.is_empty());
+    }
+
+    #[test]
+    fn insert_before_and_after() {
+        let mut list = IndexedList::new();
+
+        let five = list.push_back(5);
+        let four = list.insert_before(five, 4).unwrap();
+        let six = list.insert_after(five, 6).unwrap();
+
+        assert_eq!(list.index_of(&4), Some(four));
+        assert_eq!(list.index_of(&6), Some(six));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [4, 5, 6]);
+
+        list.remove(five);
+
+        assert_eq!(list.insert_before(five, 1), None);
+        assert!(!list.contains(&1));
+    }
+
+    #[test]
+    fn from_index_list() {
+        let mut plain = IndexList::new();
+
+        let five = plain.push_back(5);
+        let ten = plain.push_back(10);
+
+        let list = IndexedList::from(plain);
+
+        assert_eq!(list.index_of(&5), Some(five));
+        assert_eq!(list.index_of(&10), Some(ten));
+        assert_eq!(list.into_list().get(ten), Some(&10));
+    }
+}
@@ -23,15 +23,22 @@
 //! `LinkedList<T>` are provided. But some other details:
 //!
 //! * The list keeps track of its head and tail for efficient insertion.
-//! * The underlying vector only grows, never shrinks. When a node is removed, its
-//!   entry is marked as free for future insertions.
+//! * The underlying vector only grows, unless you call `compact`. When a
+//!   node is removed, its entry is marked as free for future insertions.
 //! * Free entries are themselves kept as a singly-linked list, meaning that they
 //!   can be re-used efficiently.
 //!
+//! # Fast lookups
+//!
+//! `index_of` and `contains` have to walk the list. If you need them to be
+//! fast, turn on the `indexed` feature, which provides `IndexedList<T>`. It
+//! keeps a map from items to their indexes alongside the list.
+//!
 //! # Missing features
 //!
-//! Right now, I've only implemented a minimal number of features; there's `iter`
-//! and `into_iter` but no `iter_mut`. This is on the to-do list. PRs welcome!
+//! There's still no plain `iter_mut`. In the meantime, `iter_mut_indexed` gives
+//! you a mutable reference to every item along with its `Index`, and
+//! `range_mut` does the same for just part of the list. PRs welcome!
 //!
 //! # Examples
 //!
@@ -90,6 +97,11 @@
 #![deny(unsafe_code)]
 use std::marker::PhantomData;
 
+#[cfg(feature = "indexed")]
+mod indexed;
+#[cfg(feature = "indexed")]
+pub use indexed::IndexedList;
+
 /// A doubly linked list, backed by a vector.
 ///
 /// See the crate documentation for more.
@@ -100,12 +112,41 @@ pub struct IndexList<T> {
     next_free: Option<usize>,
     head: Option<usize>,
     tail: Option<usize>,
+    len: usize,
+    tombstones: usize,
+    auto_trim: bool,
+    // removals since auto trim last ran, so that it only runs once enough has
+    // changed to pay for walking the free list
+    removals_since_trim: usize,
+    free_strategy: FreeStrategy,
+    // the last slot on the free list, so that `Fifo` can add to the end of it
+    free_tail: Option<usize>,
+}
+
+/// Which free slot an `IndexList` fills first when it needs one.
+///
+/// This is set with `with_free_strategy`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum FreeStrategy {
+    /// The most recently freed slot is reused first. This is the default.
+    #[default]
+    Lifo,
+    /// The slot that has been free the longest is reused first.
+    Fifo,
 }
 
 #[derive(Debug, PartialEq)]
 enum Entry<T> {
-    Free { next_free: Option<usize> },
+    Free {
+        next_free: Option<usize>,
+    },
     Occupied(OccupiedEntry<T>),
+    // an item removed by `remove_lazy`, which is still linked into the list
+    // until the next `sweep`
+    Tombstone {
+        next: Option<usize>,
+        prev: Option<usize>,
+    },
 }
 
 #[derive(Debug, PartialEq)]
@@ -162,13 +203,45 @@ struct OccupiedEntry<T> {
 ///
 /// assert_eq!(Some(five), index);
 /// ```
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, PartialEq)]
 pub struct Index<T> {
     index: usize,
     generation: usize,
     _marker: PhantomData<T>,
 }
 
+// these are implemented by hand, since deriving them would require that `T` is
+// `Copy` too, and an `Index` is copyable no matter what it points to
+impl<T> Clone for Index<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Index<T> {}
+
+/// Shows an index as its slot and generation, like `#3@g1` for slot 3,
+/// generation 1.
+///
+/// # Examples
+///
+/// Printing an index:
+///
+/// ```
+/// use indexlist::IndexList;
+///
+/// let mut list = IndexList::new();
+///
+/// let five = list.push_back(5);
+///
+/// assert_eq!(format!("{}", five), "#0@g0");
+/// ```
+impl<T> std::fmt::Display for Index<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#{}@g{}", self.index, self.generation)
+    }
+}
+
 impl<T> Index<T> {
     fn new(index: usize, generation: usize) -> Index<T> {
         Index {
@@ -177,469 +250,739 @@ impl<T> Index<T> {
             _marker: PhantomData,
         }
     }
-}
 
-impl<T> Default for IndexList<T> {
-    fn default() -> Self {
-        IndexList {
-            contents: Default::default(),
-            generation: Default::default(),
-            next_free: Default::default(),
-            head: Default::default(),
-            tail: Default::default(),
-        }
+    /// Reinterprets this `Index<T>` as an `Index<U>`.
+    ///
+    /// An `Index` is just a position and a generation; the type parameter only
+    /// exists to stop you from mixing up indexes from lists of different
+    /// types. This lets you opt out of that check, for example to store
+    /// indexes from several lists in one collection.
+    ///
+    /// Using a cast index with a list it didn't come from is meaningless: it
+    /// may return `None`, or it may return some unrelated item. It will never
+    /// cause memory unsafety, though.
+    ///
+    /// # Examples
+    ///
+    /// Casting an index and back again:
+    ///
+    /// ```
+    /// use indexlist::{Index, IndexList};
+    ///
+    /// let mut list: IndexList<i32> = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let erased: Index<()> = five.cast();
+    ///
+    /// assert_eq!(list.get(erased.cast()), Some(&5));
+    /// ```
+    pub fn cast<U>(self) -> Index<U> {
+        Index::new(self.index, self.generation)
     }
-}
 
-impl<T> IndexList<T>
-where
-    T: PartialEq,
-    T: std::fmt::Debug,
-{
-    /// Creates a new `IndexList<T>`.
+    /// Breaks this `Index` up into its slot and generation.
+    ///
+    /// This is useful for storing an index somewhere, and then rebuilding it
+    /// later with `from_raw_parts_checked`.
     ///
     /// # Examples
     ///
-    /// Making a new list:
+    /// Taking an index apart and putting it back together:
     ///
     /// ```
-    /// use indexlist::IndexList;
+    /// use indexlist::{Index, IndexList};
     ///
-    /// let list: IndexList<i32> = IndexList::new();
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let (slot, generation) = five.into_raw_parts();
+    /// let index = Index::from_raw_parts_checked(slot, generation, list.capacity());
+    ///
+    /// assert_eq!(index, Some(five));
     /// ```
-    pub fn new() -> IndexList<T> {
-        Self::default()
+    pub fn into_raw_parts(self) -> (usize, usize) {
+        (self.index, self.generation)
     }
 
-    /// Creates a new `IndexList<T>` with a given capacity.
+    /// Rebuilds an `Index` from a slot and a generation, checking the slot
+    /// against the size of the list you plan to use it with.
     ///
-    /// If you know roughly how many elements will be stored in the list,
-    /// creating one with that capacity can reduce allocations, increasing
-    /// performance.
+    /// Returns `None` if `index` isn't less than `list_len`. Since slots are
+    /// reused, an item's slot can be bigger than the list's `len`, so pass the
+    /// list's `capacity` if you don't want to reject any valid indexes. This is
+    /// only a cheap first check: an index that passes it can still be stale,
+    /// and a later `get` will still check its generation.
     ///
     /// # Examples
     ///
-    /// Making a new list:
+    /// Rejecting a slot that's too big:
     ///
     /// ```
-    /// use indexlist::IndexList;
+    /// use indexlist::Index;
     ///
-    /// let list: IndexList<i32> = IndexList::with_capacity(100);
+    /// let index: Option<Index<i32>> = Index::from_raw_parts_checked(5, 0, 3);
+    ///
+    /// assert!(index.is_none());
     /// ```
-    pub fn with_capacity(size: usize) -> IndexList<T> {
-        IndexList {
-            contents: Vec::with_capacity(size),
-            generation: 0,
-            next_free: None,
-            head: None,
-            tail: None,
+    pub fn from_raw_parts_checked(
+        index: usize,
+        generation: usize,
+        list_len: usize,
+    ) -> Option<Index<T>> {
+        if index < list_len {
+            Some(Index::new(index, generation))
+        } else {
+            None
         }
     }
 
-    /// Returns a reference to the first item in the list.
+    /// Checks whether this index could possibly refer to something in this
+    /// list, without looking at generations.
     ///
-    /// Will return `None` if the list is empty.
+    /// If this returns `false`, the index definitely isn't valid for the list.
+    /// If it returns `true`, you still need to `get` it to find out for sure.
     ///
     /// # Examples
     ///
-    /// The first item is often the first one that's pushed on:
+    /// An index from a longer list:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
-    /// let mut list = IndexList::new();
+    /// let mut long = IndexList::new();
     ///
-    /// list.push_back(5);
+    /// long.push_back(5);
+    /// let ten = long.push_back(10);
     ///
-    /// assert_eq!(list.head(), Some(&5));
+    /// let mut short = IndexList::new();
+    ///
+    /// short.push_back(15);
+    ///
+    /// assert!(ten.looks_valid_for(&long));
+    /// assert!(!ten.looks_valid_for(&short));
     /// ```
+    pub fn looks_valid_for(&self, list: &IndexList<T>) -> bool {
+        self.index < list.contents.len()
+    }
+
+    /// Returns the slot this index points at.
     ///
-    /// But of course, not always!
+    /// This is the position in the list's underlying vector, not the position
+    /// in the list. It's mostly useful for debugging.
+    ///
+    /// # Examples
+    ///
+    /// The first item goes into the first slot:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// list.push_back(5);
-    ///
-    /// // this will append to the front, so it's now the head
-    /// list.push_front(10);
+    /// let five = list.push_back(5);
     ///
-    /// assert_eq!(list.head(), Some(&10));
+    /// assert_eq!(five.slot(), 0);
     /// ```
-    pub fn head(&self) -> Option<&T> {
-        let index = self.head?;
-
-        self.contents.get(index).and_then(|e| match e {
-            Entry::Free { .. } => None,
-            Entry::Occupied(e) => Some(&e.item),
-        })
+    pub fn slot(&self) -> usize {
+        self.index
     }
 
-    /// Returns a mutable reference to the first item in the list.
+    /// Returns the generation of this index.
     ///
-    /// Will return `None` if the list is empty.
+    /// An index only refers to an item if the item in its slot has the same
+    /// generation. This is mostly useful for debugging why an index is stale.
     ///
     /// # Examples
     ///
-    /// The first item is often the first one that's pushed on:
+    /// A reused slot gets a newer generation:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// list.push_back(5);
+    /// let five = list.push_back(5);
+    /// list.remove(five);
     ///
-    /// assert_eq!(list.head_mut(), Some(&mut 5));
+    /// let ten = list.push_back(10);
+    ///
+    /// assert_eq!(five.slot(), ten.slot());
+    /// assert!(five.generation() < ten.generation());
     /// ```
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Packs this `Index` into a single `u128`, with the slot in the high 64
+    /// bits and the generation in the low 64 bits.
     ///
-    /// But of course, not always!
+    /// This assumes that `usize` is no more than 64 bits wide, which is true
+    /// on every platform Rust currently supports.
+    ///
+    /// # Examples
+    ///
+    /// Packing an index and unpacking it again:
     ///
     /// ```
-    /// use indexlist::IndexList;
+    /// use indexlist::{Index, IndexList};
     ///
     /// let mut list = IndexList::new();
     ///
-    /// list.push_back(5);
+    /// let five = list.push_back(5);
     ///
-    /// // this will append to the front, so it's now the head
-    /// list.push_front(10);
+    /// let packed = five.as_u128();
     ///
-    /// assert_eq!(list.head_mut(), Some(&mut 10));
+    /// assert_eq!(list.get(Index::from_u128(packed)), Some(&5));
     /// ```
-    pub fn head_mut(&mut self) -> Option<&mut T> {
-        let index = self.head?;
-
-        match &mut self.contents[index] {
-            Entry::Free { .. } => None,
-            Entry::Occupied(e) => Some(&mut e.item),
-        }
-    }
-
-    pub fn head_index(&self) -> Option<Index<T>> {
-        let index = self.head?;
-
-        self.contents.get(index).and_then(|e| match e {
-            Entry::Free { .. } => None,
-            Entry::Occupied(e) => Some(Index::new(index, e.generation)),
-        })
+    pub fn as_u128(&self) -> u128 {
+        ((self.index as u128) << 64) | (self.generation as u128)
     }
 
-    pub fn tail_index(&self) -> Option<Index<T>> {
-        let index = self.tail?;
-
-        self.contents.get(index).and_then(|e| match e {
-            Entry::Free { .. } => None,
-            Entry::Occupied(e) => Some(Index::new(index, e.generation)),
-        })
+    /// Unpacks an `Index` from a `u128` made by `as_u128`.
+    ///
+    /// Like an index from `from_raw_parts_checked`, this isn't checked
+    /// against any list, but using it with the wrong one just gives you
+    /// `None` or an unrelated item.
+    pub fn from_u128(packed: u128) -> Index<T> {
+        Index::new((packed >> 64) as usize, packed as u64 as usize)
     }
 
-    /// Adds this item to the tail of the list.
+    /// Returns an `Index` that never refers to an item, in any list.
+    ///
+    /// This is useful as a placeholder when you need an `Index` but don't have
+    /// one yet. Looking it up always gives you `None`.
     ///
     /// # Examples
     ///
-    /// Pushing several numbers into a list:
+    /// A dangling index doesn't find anything:
     ///
     /// ```
-    /// use indexlist::IndexList;
+    /// use indexlist::{Index, IndexList};
     ///
     /// let mut list = IndexList::new();
     ///
     /// list.push_back(5);
-    /// list.push_back(10);
-    /// list.push_back(15);
     ///
-    /// // This prints 5, 10, and then 15, each on its own line
-    /// for element in list.iter() {
-    ///     println!("{}", element);
-    /// }
+    /// assert_eq!(list.get(Index::dangling()), None);
     /// ```
-    pub fn push_back(&mut self, item: T) -> Index<T> {
-        // first, we need to find a suitable place to insert
-
-        // is the head of the list empty? If so, that's easy.
-        if self.head.is_none() {
-            let generation = self.generation;
+    pub fn dangling() -> Index<T> {
+        // no vector can hold this many entries, so this slot is never in
+        // bounds
+        Index::new(usize::MAX, usize::MAX)
+    }
+}
 
-            let index = if let Some(index) = self.next_free {
-                match self.contents[index] {
-                    Entry::Occupied { .. } => panic!("Corrupted list"),
-                    Entry::Free { next_free } => self.next_free = next_free,
-                }
+/// A stable identity for a node in an `IndexList<T>`.
+///
+/// You get one from `IndexList::id`, and turn it back into an `Index` with
+/// `IndexList::resolve`. A `NodeId` names a node rather than a position or a
+/// value, so:
+///
+/// * Moving a node around the list, with methods like `move_before`,
+///   `move_after`, or `swap_with_next`, keeps its `NodeId` valid.
+/// * Changing the node's item, or swapping items between nodes like
+///   `swap_ends` does, keeps it valid too; it still names the same node, which
+///   now holds a different item.
+/// * Removing the node invalidates it, even if its slot is later reused.
+/// * Anything that moves nodes to new slots or restamps the list, like
+///   `compact` or `swap_contents`, invalidates every `NodeId` at once.
+///
+/// # Examples
+///
+/// Keeping track of a node while it moves:
+///
+/// ```
+/// use indexlist::IndexList;
+///
+/// let mut list = IndexList::new();
+///
+/// let one = list.push_back(1);
+/// let two = list.push_back(2);
+///
+/// let id = list.id(two);
+///
+/// list.move_before(two, one);
+///
+/// assert_eq!(list.resolve(id), Some(two));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct NodeId<T>(Index<T>);
 
-                self.contents[index] = Entry::Occupied(OccupiedEntry {
-                    item,
-                    generation,
-                    next: None,
-                    prev: None,
-                });
+impl<T> Clone for NodeId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 
-                index
-            } else {
-                let index = self.contents.len();
+impl<T> Copy for NodeId<T> {}
 
-                self.contents.push(Entry::Occupied(OccupiedEntry {
-                    item,
-                    generation,
-                    next: None,
-                    prev: None,
-                }));
+/// The reason an insertion next to an `Index` failed.
+///
+/// This is returned by `try_insert_before` and `try_insert_after`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InsertError {
+    /// The index refers to a slot past the end of the list's storage, most
+    /// likely because it came from a different list.
+    OutOfBounds,
+    /// The item this index referred to was removed, and its slot has since
+    /// been reused by another item.
+    StaleGeneration,
+    /// The item this index referred to was removed, and its slot is empty.
+    SlotFree,
+}
 
-                index
-            };
+impl std::fmt::Display for InsertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let message = match self {
+            InsertError::OutOfBounds => "index is out of bounds",
+            InsertError::StaleGeneration => "index is from an older generation",
+            InsertError::SlotFree => "index refers to a removed item",
+        };
 
-            self.tail = Some(index);
-            self.head = Some(index);
+        f.write_str(message)
+    }
+}
 
-            return Index::new(index, generation);
-        }
+impl std::error::Error for InsertError {}
 
-        // if it isn't empty, then we need to check the free list and put our
-        // new item in the proper place
+/// The reason an `Index` couldn't be looked up.
+///
+/// This is returned by `get_checked`. Each variant matches one of
+/// `InsertError`'s, which is worked out the same way.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IndexError {
+    /// There's no slot this far into the list. See `InsertError::OutOfBounds`.
+    OutOfBounds,
+    /// The slot now holds a newer item. See `InsertError::StaleGeneration`.
+    Stale,
+    /// Nothing is stored in the slot. See `InsertError::SlotFree`.
+    Freed,
+}
 
-        // we have a tail, so we can unwrap; we need this for appending
-        let tail_index = self.tail.unwrap();
+impl std::fmt::Display for IndexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let message = match self {
+            IndexError::OutOfBounds => "no slot at this index",
+            IndexError::Stale => "slot has been reused since this index was made",
+            IndexError::Freed => "slot at this index is empty",
+        };
 
-        let position = if let Some(position) = self.next_free {
-            // update next_free
-            match self.contents[position] {
-                Entry::Occupied { .. } => panic!("Corrupted list"),
-                Entry::Free { next_free } => self.next_free = next_free,
-            }
+        f.write_str(message)
+    }
+}
 
-            self.contents[position] = Entry::Occupied(OccupiedEntry {
-                item,
-                generation: self.generation,
-                next: None,
-                prev: Some(tail_index),
-            });
+impl std::error::Error for IndexError {}
 
-            position
-        } else {
-            // we don't have any, so append to the end of the list
-            let position = self.contents.len();
+impl<T> Default for IndexList<T> {
+    fn default() -> Self {
+        IndexList {
+            contents: Default::default(),
+            generation: Default::default(),
+            next_free: Default::default(),
+            head: Default::default(),
+            tail: Default::default(),
+            len: Default::default(),
+            tombstones: Default::default(),
+            auto_trim: Default::default(),
+            removals_since_trim: Default::default(),
+            free_strategy: Default::default(),
+            free_tail: Default::default(),
+        }
+    }
+}
 
-            self.contents.push(Entry::Occupied(OccupiedEntry {
-                item,
-                generation: self.generation,
-                next: None,
-                prev: Some(tail_index),
-            }));
+impl<T> IndexList<T> {
+    // follows `next` links past any tombstones, returning the first slot from
+    // `slot` onwards that holds an item
+    fn live_forward(&self, mut slot: Option<usize>) -> Option<usize> {
+        while let Some(index) = slot {
+            match self.contents[index] {
+                Entry::Tombstone { next, .. } => slot = next,
+                _ => return slot,
+            }
+        }
 
-            position
-        };
+        None
+    }
 
-        // and then fix up the tail to refer to it
-        let new_index = Index::new(position, self.generation);
+    // like `live_forward`, but follows `prev` links instead
+    fn live_backward(&self, mut slot: Option<usize>) -> Option<usize> {
+        while let Some(index) = slot {
+            match self.contents[index] {
+                Entry::Tombstone { prev, .. } => slot = prev,
+                _ => return slot,
+            }
+        }
 
-        // we found this index before so we know it exists
-        match &mut self.contents[tail_index] {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => e.next = Some(new_index.index),
-        }
-
-        // update our tail to properly point at the newly inserted element
-        self.tail = Some(position);
-
-        // and finally, return the index associated with our new tail
-        new_index
+        None
     }
 
-    /// Adds this item to the head of the list.
+    /// Returns how many of these indexes still refer to an item in the list.
+    ///
+    /// An index counts if `get` would return `Some` for it.
     ///
     /// # Examples
     ///
-    /// Pushing several numbers into a list:
+    /// Counting the indexes that are still good:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// list.push_front(5);
-    /// list.push_front(10);
-    /// list.push_front(15);
+    /// let five = list.push_back(5);
+    /// let ten = list.push_back(10);
     ///
-    /// // This prints 15, 10, and then 5, each on its own line
-    /// for element in list.iter() {
-    ///     println!("{}", element);
-    /// }
+    /// list.remove(five);
+    ///
+    /// assert_eq!(list.count_valid(&[five, ten]), 1);
     /// ```
-    pub fn push_front(&mut self, item: T) -> Index<T> {
-        // first, we need to find a suitable place to insert
+    pub fn count_valid(&self, indexes: &[Index<T>]) -> usize {
+        indexes
+            .iter()
+            .filter(|index| match self.contents.get(index.index) {
+                Some(Entry::Occupied(e)) => e.generation == index.generation,
+                _ => false,
+            })
+            .count()
+    }
+}
 
-        // is the head of the list empty? If so, that's easy.
-        if self.head.is_none() {
-            return self.push_back(item);
+impl<T> IndexList<T>
+where
+    T: PartialEq,
+    T: std::fmt::Debug,
+{
+    /// Creates a new `IndexList<T>`.
+    ///
+    /// # Examples
+    ///
+    /// Making a new list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list: IndexList<i32> = IndexList::new();
+    /// ```
+    pub fn new() -> IndexList<T> {
+        Self::default()
+    }
+
+    /// Creates a new `IndexList<T>` with a given capacity.
+    ///
+    /// If you know roughly how many elements will be stored in the list,
+    /// creating one with that capacity can reduce allocations, increasing
+    /// performance.
+    ///
+    /// # Examples
+    ///
+    /// Making a new list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list: IndexList<i32> = IndexList::with_capacity(100);
+    /// ```
+    pub fn with_capacity(size: usize) -> IndexList<T> {
+        IndexList {
+            contents: Vec::with_capacity(size),
+            generation: 0,
+            next_free: None,
+            head: None,
+            tail: None,
+            len: 0,
+            tombstones: 0,
+            auto_trim: false,
+            removals_since_trim: 0,
+            free_strategy: FreeStrategy::Lifo,
+            free_tail: None,
         }
+    }
 
-        // if it isn't empty, then we need to check the free list and put our
-        // new item in the proper place
+    /// Creates a new `IndexList<T>` with a given capacity, whose generation
+    /// starts at `generation` instead of zero.
+    ///
+    /// Items pushed into the list are stamped with its generation, so this is
+    /// useful for rebuilding a list so that indexes you saved from an older
+    /// one line up again.
+    ///
+    /// # Examples
+    ///
+    /// Starting at a later generation:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::with_capacity_and_generation(10, 7);
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// assert_eq!(five.generation(), 7);
+    /// ```
+    pub fn with_capacity_and_generation(capacity: usize, generation: usize) -> IndexList<T> {
+        let mut list = IndexList::with_capacity(capacity);
+        list.generation = generation;
 
-        // we have a head, so we can unwrap; we need this for appending
-        let head_index = self.head.unwrap();
+        list
+    }
 
-        let position = if let Some(position) = self.next_free {
-            // update next_free
-            match self.contents[position] {
-                Entry::Occupied { .. } => panic!("Corrupted list"),
-                Entry::Free { next_free } => self.next_free = next_free,
-            }
+    /// Creates a new `IndexList<T>` containing a single item.
+    ///
+    /// # Examples
+    ///
+    /// Making a list of one:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::singleton(5);
+    ///
+    /// assert_eq!(list.head(), Some(&5));
+    /// ```
+    pub fn singleton(item: T) -> IndexList<T> {
+        Self::singleton_with_index(item).0
+    }
 
-            self.contents[position] = Entry::Occupied(OccupiedEntry {
-                item,
-                generation: self.generation,
-                next: Some(head_index),
-                prev: None,
-            });
+    /// Creates a new `IndexList<T>` containing a single item, and returns the
+    /// item's `Index` along with it.
+    ///
+    /// # Examples
+    ///
+    /// Making a list of one:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let (list, five) = IndexList::singleton_with_index(5);
+    ///
+    /// assert_eq!(list.get(five), Some(&5));
+    /// ```
+    pub fn singleton_with_index(item: T) -> (IndexList<T>, Index<T>) {
+        let mut list = IndexList::with_capacity(1);
+        let index = list.push_back(item);
 
-            position
-        } else {
-            // we don't have any, so append to the end of the list
-            let position = self.contents.len();
+        (list, index)
+    }
 
-            self.contents.push(Entry::Occupied(OccupiedEntry {
-                item,
-                generation: self.generation,
-                next: Some(head_index),
-                prev: None,
-            }));
+    /// Creates a new `IndexList<T>` containing `n` copies of an item.
+    ///
+    /// # Examples
+    ///
+    /// Making a list of three sevens:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::repeat(7, 3);
+    ///
+    /// assert_eq!(list.len(), 3);
+    /// assert!(list.iter().all(|&n| n == 7));
+    /// ```
+    pub fn repeat(item: T, n: usize) -> IndexList<T>
+    where
+        T: Clone,
+    {
+        let mut list = IndexList::with_capacity(n);
+
+        if n == 0 {
+            return list;
+        }
 
-            position
-        };
+        for _ in 1..n {
+            list.push_back(item.clone());
+        }
 
-        let new_index = Index::new(position, self.generation);
+        // the last one doesn't need a clone
+        list.push_back(item);
 
-        // and then fix up the head to refer to it
+        list
+    }
 
-        // we found this index before so we know it exists
-        match &mut self.contents[head_index] {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => e.prev = Some(new_index.index),
+    /// Creates a new `IndexList<T>` containing clones of the items in a
+    /// slice, in the same order.
+    ///
+    /// # Examples
+    ///
+    /// Making a list from a slice:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    pub fn from_slice(items: &[T]) -> IndexList<T>
+    where
+        T: Clone,
+    {
+        let mut list = IndexList::with_capacity(items.len());
+
+        for item in items {
+            list.push_back(item.clone());
         }
 
-        // update our head to properly point at the newly inserted element
-        self.head = Some(position);
-
-        // and finally, return the index associated with our new tail
-        new_index
+        list
     }
 
-    /// Does this list contain this element?
+    /// Creates a new `IndexList<T>` from `(position, item)` pairs, which can
+    /// come in any order.
     ///
-    /// Returns true if it does, and false if it does not.
+    /// The items are put into the list sorted by position. Gaps between
+    /// positions are ignored, and items with the same position keep the order
+    /// they came in.
     ///
     /// # Examples
     ///
-    /// Checking both possibilities:
+    /// Rebuilding a list from shuffled pairs:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
-    /// let mut list = IndexList::new();
-    ///
-    /// list.push_back(5);
-    ///
-    /// // our list does contain five
-    /// assert!(list.contains(&5));
+    /// let list = IndexList::from_positioned(vec![(2, 'c'), (0, 'a'), (1, 'b')]);
     ///
-    /// // our list does not contain ten
-    /// assert!(!list.contains(&10));
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), ['a', 'b', 'c']);
     /// ```
-    pub fn contains(&self, value: &T) -> bool {
-        self.iter().any(|e| e == value)
+    pub fn from_positioned<I>(pairs: I) -> IndexList<T>
+    where
+        I: IntoIterator<Item = (usize, T)>,
+    {
+        let mut pairs: Vec<(usize, T)> = pairs.into_iter().collect();
+
+        // a stable sort, so equal positions stay in the order they came in
+        pairs.sort_by_key(|&(position, _)| position);
+
+        let mut list = IndexList::with_capacity(pairs.len());
+
+        for (_, item) in pairs {
+            list.push_back(item);
+        }
+
+        list
     }
 
-    /// Returns the item at this index if it exists.
-    ///
-    /// If there's an item at this index, then this will return a reference to
-    /// it. If not, returns `None`.
-    ///
-    /// Indexes are generational, and so this method will use the generation to
-    /// determine if this element exists. For more, see [`Index`'s documentation].
+    /// Sets whether this list trims its free slots automatically.
     ///
-    /// [`Index`'s documentation]: struct.Index.html
+    /// When this is enabled, removing an item from the list will call
+    /// `trim_trailing_free` once more than half of the slots in the list are
+    /// free. To keep removal cheap on average, it won't do that again until
+    /// there have been a quarter as many removals as there are slots, unless
+    /// the list is empty. This keeps memory use bounded for long-lived lists,
+    /// at the cost of some extra work on removal. It's off by default.
     ///
     /// # Examples
     ///
-    /// Getting an element at an index:
+    /// Making a list that trims itself:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
-    /// let mut list = IndexList::new();
+    /// let mut list = IndexList::new().with_auto_trim(true);
     ///
-    /// let five = list.push_back(5);
+    /// for i in 0..100 {
+    ///     list.push_back(i);
+    /// }
     ///
-    /// assert_eq!(list.get(five), Some(&5));
+    /// while list.pop_back().is_some() {}
+    ///
+    /// assert!(list.capacity() < 100);
     /// ```
+    pub fn with_auto_trim(mut self, enabled: bool) -> Self {
+        self.auto_trim = enabled;
+        self
+    }
+
+    /// Sets which free slot this list fills first when it needs one, using a
+    /// [`FreeStrategy`].
     ///
-    /// An element that doesn't exist returns `None`:
+    /// With `FreeStrategy::Lifo`, which is the default, the most recently
+    /// freed slot is reused first. With `FreeStrategy::Fifo`, the slot that
+    /// has been free the longest is reused first. Either way, only slots freed
+    /// after this call are affected; ones that are already free keep their
+    /// place in line.
+    ///
+    /// [`FreeStrategy`]: enum.FreeStrategy.html
+    ///
+    /// # Examples
+    ///
+    /// Reusing slots in the order they were freed:
     ///
     /// ```
-    /// use indexlist::IndexList;
+    /// use indexlist::{FreeStrategy, IndexList};
     ///
-    /// let mut list = IndexList::new();
+    /// let mut list = IndexList::new().with_free_strategy(FreeStrategy::Fifo);
     ///
     /// let five = list.push_back(5);
+    /// let ten = list.push_back(10);
     ///
     /// list.remove(five);
+    /// list.remove(ten);
     ///
-    /// assert!(list.get(five).is_none());
+    /// // five's slot was freed first, so it's reused first
+    /// let fifteen = list.push_back(15);
+    ///
+    /// assert_eq!(fifteen.slot(), five.slot());
     /// ```
+    pub fn with_free_strategy(mut self, strategy: FreeStrategy) -> Self {
+        self.free_strategy = strategy;
+        self
+    }
+
+    /// Returns the number of items in the list.
     ///
-    /// Generational indexes ensure that we don't access incorrect items:
+    /// # Examples
+    ///
+    /// Counting the items:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// let five = list.push_back(5);
-    /// list.push_back(10);
-    ///
-    /// list.remove(five);
+    /// assert_eq!(list.len(), 0);
     ///
-    /// // since we have a free spot, this will go where 5 was
-    /// list.push_back(15);
+    /// list.push_back(5);
+    /// list.push_back(10);
     ///
-    /// // our index is out of date, and so will not return 15 here
-    /// assert!(list.get(five).is_none());
+    /// assert_eq!(list.len(), 2);
     /// ```
-    pub fn get(&self, index: Index<T>) -> Option<&T> {
-        match self.contents.get(index.index)? {
-            Entry::Occupied(e) if e.generation == index.generation => Some(&e.item),
-            _ => None,
-        }
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    /// Returns the item at this index if it exists.
-    ///
-    /// If there's an item at this index, then this will return a mutable reference to
-    /// it. If not, returns `None`.
-    ///
-    /// Indexes are generational, and so this method will use the generation to
-    /// determine if this element exists. For more, see [`Index`'s documentation].
-    ///
-    /// [`Index`'s documentation]: struct.Index.html
+    /// Returns true if there are no items in the list.
     ///
     /// # Examples
     ///
-    /// Getting an element at an index:
+    /// Checking both possibilities:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// let five = list.push_back(5);
+    /// assert!(list.is_empty());
     ///
-    /// assert_eq!(list.get_mut(five), Some(&mut 5));
+    /// list.push_back(5);
+    ///
+    /// assert!(!list.is_empty());
     /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the list's current generation.
     ///
-    /// An element that doesn't exist returns `None`:
+    /// The generation goes up whenever an item is removed or the list is
+    /// compacted, and new items are stamped with it.
+    /// Pushing and inserting don't change it, so if it's the same at two points
+    /// in time, nothing was removed in between.
+    ///
+    /// # Examples
+    ///
+    /// Noticing a removal:
     ///
     /// ```
     /// use indexlist::IndexList;
@@ -648,393 +991,423 @@ where
     ///
     /// let five = list.push_back(5);
     ///
+    /// let before = list.generation();
+    ///
     /// list.remove(five);
     ///
-    /// assert!(list.get_mut(five).is_none());
+    /// assert!(list.generation() > before);
     /// ```
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Returns the number of items the list can hold without reallocating.
     ///
-    /// Generational indexes ensure that we don't access incorrect items:
+    /// # Examples
+    ///
+    /// Checking the capacity of a new list:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
-    /// let mut list = IndexList::new();
+    /// let list: IndexList<i32> = IndexList::with_capacity(10);
     ///
-    /// let five = list.push_back(5);
-    /// list.push_back(10);
+    /// assert!(list.capacity() >= 10);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.contents.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more items.
     ///
-    /// list.remove(five);
+    /// This may reserve more space than asked for, to avoid frequent
+    /// reallocations. Free slots left behind by removals aren't counted, so
+    /// you may end up with more room than you need.
     ///
-    /// // since we have a free spot, this will go where 5 was
-    /// list.push_back(15);
+    /// # Examples
+    ///
+    /// Reserving some space:
     ///
-    /// // our index is out of date, and so will not return 15 here
-    /// assert!(list.get_mut(five).is_none());
     /// ```
-    pub fn get_mut(&mut self, index: Index<T>) -> Option<&mut T> {
-        match &mut self.contents[index.index] {
-            Entry::Occupied(e) if e.generation == index.generation => Some(&mut e.item),
-            _ => None,
-        }
-    }
-
-    pub fn next_index(&self, index: Index<T>) -> Option<Index<T>> {
-        match self.contents.get(index.index)? {
-            Entry::Occupied(e) if e.generation == index.generation => {
-                match e.next {
-                    Some(index) => match self.contents.get(index)? {
-                        Entry::Occupied(e) => Some(Index::new(index, e.generation)),
-                        _ => panic!("Corrupted list"),
-                    },
-                    _ => None, // this element was at the end of the list
-                }
-            }
-            _ => None, // this was an invalid or outdated index
-        }
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list: IndexList<i32> = IndexList::new();
+    ///
+    /// list.reserve(10);
+    ///
+    /// assert!(list.capacity() >= 10);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.contents.reserve(additional);
     }
 
-    pub fn prev_index(&self, index: Index<T>) -> Option<Index<T>> {
-        match self.contents.get(index.index)? {
-            Entry::Occupied(e) if e.generation == index.generation => {
-                match e.prev {
-                    Some(index) => match self.contents.get(index)? {
-                        Entry::Occupied(e) => Some(Index::new(index, e.generation)),
-                        _ => panic!("Corrupted list"),
-                    },
-                    _ => None, // this element was at the end of the list
-                }
-            }
-            _ => None, // this was an invalid or outdated index
-        }
+    /// Reserves capacity for exactly `additional` more items.
+    ///
+    /// Unlike `reserve`, this won't deliberately over-allocate. The allocator
+    /// may still give us more than we asked for, though.
+    ///
+    /// # Examples
+    ///
+    /// Reserving some space:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list: IndexList<i32> = IndexList::new();
+    ///
+    /// list.reserve_exact(10);
+    ///
+    /// assert!(list.capacity() >= 10);
+    /// ```
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.contents.reserve_exact(additional);
     }
 
-    /// Removes the item at this index, and returns the removed item.
+    /// Returns a reference to the first item in the list.
     ///
-    /// If there's an item at this index, then this will remove it from the list
-    /// and return it. If there isn't, it will return `None`.
+    /// Will return `None` if the list is empty.
     ///
-    /// Indexes are generational, and so this method will use the generation to
-    /// determine if this element exists. For more, see [`Index`'s documentation].
+    /// # Examples
     ///
-    /// [`Index`'s documentation]: struct.Index.html
+    /// The first item is often the first one that's pushed on:
     ///
-    /// # Examples
+    /// ```
+    /// use indexlist::IndexList;
     ///
-    /// Removing an element from an index:
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    ///
+    /// assert_eq!(list.head(), Some(&5));
+    /// ```
+    ///
+    /// But of course, not always!
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// let five = list.push_back(5);
+    /// list.push_back(5);
     ///
-    /// let five = list.remove(five);
-    /// assert_eq!(five, Some(5));
+    /// // this will append to the front, so it's now the head
+    /// list.push_front(10);
     ///
-    /// assert!(!list.contains(&5));
+    /// assert_eq!(list.head(), Some(&10));
     /// ```
+    pub fn head(&self) -> Option<&T> {
+        let index = self.live_forward(self.head)?;
+
+        self.contents.get(index).and_then(|e| match e {
+            Entry::Free { .. } | Entry::Tombstone { .. } => None,
+            Entry::Occupied(e) => Some(&e.item),
+        })
+    }
+
+    /// Returns a mutable reference to the first item in the list.
     ///
-    /// An element that doesn't exist returns `None`:
+    /// Will return `None` if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// The first item is often the first one that's pushed on:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// let five = list.push_back(5);
-    ///
-    /// list.remove(five);
+    /// list.push_back(5);
     ///
-    /// assert!(list.remove(five).is_none());
+    /// assert_eq!(list.head_mut(), Some(&mut 5));
     /// ```
     ///
-    /// Generational indexes ensure that we don't access incorrect items:
+    /// But of course, not always!
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// let five = list.push_back(5);
-    /// list.push_back(10);
+    /// list.push_back(5);
     ///
-    /// list.remove(five);
+    /// // this will append to the front, so it's now the head
+    /// list.push_front(10);
     ///
-    /// // since we have a free spot, this will go where 5 was
+    /// assert_eq!(list.head_mut(), Some(&mut 10));
+    /// ```
+    pub fn head_mut(&mut self) -> Option<&mut T> {
+        let index = self.live_forward(self.head)?;
+
+        match &mut self.contents[index] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => None,
+            Entry::Occupied(e) => Some(&mut e.item),
+        }
+    }
+
+    pub fn head_index(&self) -> Option<Index<T>> {
+        let index = self.live_forward(self.head)?;
+
+        self.contents.get(index).and_then(|e| match e {
+            Entry::Free { .. } | Entry::Tombstone { .. } => None,
+            Entry::Occupied(e) => Some(Index::new(index, e.generation)),
+        })
+    }
+
+    pub fn tail_index(&self) -> Option<Index<T>> {
+        let index = self.live_backward(self.tail)?;
+
+        self.contents.get(index).and_then(|e| match e {
+            Entry::Free { .. } | Entry::Tombstone { .. } => None,
+            Entry::Occupied(e) => Some(Index::new(index, e.generation)),
+        })
+    }
+
+    /// Adds this item to the tail of the list.
+    ///
+    /// # Examples
+    ///
+    /// Pushing several numbers into a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
     /// list.push_back(15);
     ///
-    /// // our index is out of date, and so will not return 15 here
-    /// assert!(list.remove(five).is_none());
+    /// // This prints 5, 10, and then 15, each on its own line
+    /// for element in list.iter() {
+    ///     println!("{}", element);
+    /// }
     /// ```
-    pub fn remove(&mut self, index: Index<T>) -> Option<T> {
-        // if we have no head or tail, then we have an emtpy list, so return
-        let head_index = self.head?;
-        let tail_index = self.tail?;
+    pub fn push_back(&mut self, item: T) -> Index<T> {
+        self.sweep();
 
-        // we want to do just get, but then we run into borrowing issues.
-        //
-        // we could implement Entry, but... ugh. So let's fetch just the indexes for now.
-        let (prev_index, index, next_index) = match self.contents.get(index.index)? {
-            Entry::Free { .. } => return None,
-            Entry::Occupied(e) => {
-                // are we of the right generation?
-                if index.generation != e.generation {
-                    return None;
-                }
+        // first, we need to find a suitable place to insert
 
-                (e.prev, index.index, e.next)
-            }
-        };
+        // is the head of the list empty? If so, that's easy.
+        if self.head.is_none() {
+            let generation = self.generation;
 
-        let removed = std::mem::replace(
-            &mut self.contents[index],
-            Entry::Free {
-                next_free: self.next_free,
-            },
-        );
+            let index = if let Some(index) = self.take_free() {
+                self.contents[index] = Entry::Occupied(OccupiedEntry {
+                    item,
+                    generation,
+                    next: None,
+                    prev: None,
+                });
 
-        // update our free list to point to this new space
-        self.next_free = Some(index);
+                index
+            } else {
+                let index = self.contents.len();
 
-        // when we remove a node, we need to increase the generation to invalidate
-        // older indexes that may be refering to this spot
-        self.generation += 1;
+                self.contents.push(Entry::Occupied(OccupiedEntry {
+                    item,
+                    generation,
+                    next: None,
+                    prev: None,
+                }));
 
-        // now we need to fix up any next or previous nodes. we have four cases:
-        //
-        // * index is at the head and tail (only item in the list)
-        // * index is at the head
-        // * index is at the tail
-        // * index is in the middle
-
-        // index is at the head and tail (only item in the list)
-        if (index == head_index) && (index == tail_index) {
-            self.head = None;
-            self.tail = None;
-
-        // index is at the head
-        } else if index == head_index {
-            let next = match &mut self.contents[next_index.unwrap()] {
-                Entry::Free { .. } => panic!("Corrupted list"),
-                Entry::Occupied(e) => e,
+                index
             };
 
-            next.prev = None;
-            self.head = next_index;
+            self.tail = Some(index);
+            self.head = Some(index);
+            self.len += 1;
 
-        // index is at the tail
-        } else if index == tail_index {
-            let prev = match &mut self.contents[prev_index.unwrap()] {
-                Entry::Free { .. } => panic!("Corrupted list"),
-                Entry::Occupied(e) => e,
-            };
+            return Index::new(index, generation);
+        }
 
-            prev.next = None;
-            self.tail = prev_index;
+        // if it isn't empty, then we need to check the free list and put our
+        // new item in the proper place
 
-        // index is in the middle
-        } else if index != head_index && index != tail_index {
-            // fix up next
-            {
-                let next = match &mut self.contents[next_index.unwrap()] {
-                    Entry::Free { .. } => panic!("Corrupted list"),
-                    Entry::Occupied(e) => e,
-                };
+        // we have a tail, so we can unwrap; we need this for appending
+        let tail_index = self.tail.unwrap();
 
-                next.prev = prev_index;
-            }
+        let position = if let Some(position) = self.take_free() {
+            self.contents[position] = Entry::Occupied(OccupiedEntry {
+                item,
+                generation: self.generation,
+                next: None,
+                prev: Some(tail_index),
+            });
 
-            // fix up prev
-            {
-                let prev = match &mut self.contents[prev_index.unwrap()] {
-                    Entry::Free { .. } => panic!("Corrupted list"),
-                    Entry::Occupied(e) => e,
-                };
-                prev.next = next_index;
-            }
-        }
+            position
+        } else {
+            // we don't have any, so append to the end of the list
+            let position = self.contents.len();
 
-        match removed {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => Some(e.item),
+            self.contents.push(Entry::Occupied(OccupiedEntry {
+                item,
+                generation: self.generation,
+                next: None,
+                prev: Some(tail_index),
+            }));
+
+            position
+        };
+
+        // and then fix up the tail to refer to it
+        let new_index = Index::new(position, self.generation);
+
+        // we found this index before so we know it exists
+        match &mut self.contents[tail_index] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => e.next = Some(new_index.index),
         }
+
+        // update our tail to properly point at the newly inserted element
+        self.tail = Some(position);
+        self.len += 1;
+
+        // and finally, return the index associated with our new tail
+        new_index
     }
 
-    /// Inserts an element immediately before the provided index. Returns `None`
-    /// if the element at the provided index was removed.
+    /// Adds this item to the head of the list.
     ///
-    /// # Example
+    /// # Examples
+    ///
+    /// Pushing several numbers into a list:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// let five = list.push_back(5);
-    /// list.insert_before(five, 0);
-    /// 
-    /// assert_eq!(list.head(), Some(&0));
+    /// list.push_front(5);
+    /// list.push_front(10);
+    /// list.push_front(15);
+    ///
+    /// // This prints 15, 10, and then 5, each on its own line
+    /// for element in list.iter() {
+    ///     println!("{}", element);
+    /// }
     /// ```
-    pub fn insert_before(&mut self, index: Index<T>, item: T) -> Option<Index<T>> {
-        // Get the current index
-        let (prev_index, index, _next_index) = match self.contents.get(index.index)? {
-            Entry::Free { .. } => return None,
-            Entry::Occupied(e) => {
-                // are we of the right generation?
-                if index.generation != e.generation {
-                    return None;
-                }
-                (e.prev, index.index, e.next)
-            }
-        };
-        let entry = Entry::Occupied(OccupiedEntry {
-            item,
-            generation: self.generation,
-            next: Some(index),
-            prev: prev_index,
-        });
-        // Insert the item
-        let position = if let Some(position) = self.next_free {
-            // update next_free
-            match self.contents[position] {
-                Entry::Occupied { .. } => panic!("Corrupted list"),
-                Entry::Free { next_free } => self.next_free = next_free,
-            }
-            self.contents[position] = entry;
+    pub fn push_front(&mut self, item: T) -> Index<T> {
+        self.sweep();
+
+        // first, we need to find a suitable place to insert
+
+        // is the head of the list empty? If so, that's easy.
+        if self.head.is_none() {
+            return self.push_back(item);
+        }
+
+        // if it isn't empty, then we need to check the free list and put our
+        // new item in the proper place
+
+        // we have a head, so we can unwrap; we need this for appending
+        let head_index = self.head.unwrap();
+
+        let position = if let Some(position) = self.take_free() {
+            self.contents[position] = Entry::Occupied(OccupiedEntry {
+                item,
+                generation: self.generation,
+                next: Some(head_index),
+                prev: None,
+            });
+
             position
         } else {
             // we don't have any, so append to the end of the list
             let position = self.contents.len();
-            self.contents.push(entry);
+
+            self.contents.push(Entry::Occupied(OccupiedEntry {
+                item,
+                generation: self.generation,
+                next: Some(head_index),
+                prev: None,
+            }));
+
             position
         };
-        match &mut self.contents[index] {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => {
-                e.prev = Some(position);
-            }
-        }
-        // Now, we need to update the prev node, if there was one, as well as
-        // the head, if there wasn't
-        match prev_index {
-            Some(index) => match &mut self.contents[index] {
-                Entry::Occupied(e) => {
-                    e.next = Some(position);
-                }
-                _ => panic!("Corrupted list"),
-            },
-            None => {
-                self.head = Some(position);
-            }
+
+        let new_index = Index::new(position, self.generation);
+
+        // and then fix up the head to refer to it
+
+        // we found this index before so we know it exists
+        match &mut self.contents[head_index] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => e.prev = Some(new_index.index),
         }
-        Some(Index::new(position, self.generation))
+
+        // update our head to properly point at the newly inserted element
+        self.head = Some(position);
+        self.len += 1;
+
+        // and finally, return the index associated with our new tail
+        new_index
     }
 
-    /// Inserts an element immediately after the provided index. Returns `None`
-    /// if the element at the provided index was removed.
+    /// Does this list contain this element?
     ///
-    /// # Example
+    /// Returns true if it does, and false if it does not.
+    ///
+    /// # Examples
+    ///
+    /// Checking both possibilities:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// let five = list.push_back(5);
-    /// list.insert_after(five, 0);
-    /// 
-    /// assert_eq!(list.tail_index().and_then(|tail| list.get(tail)), Some(&0));
+    /// list.push_back(5);
+    ///
+    /// // our list does contain five
+    /// assert!(list.contains(&5));
+    ///
+    /// // our list does not contain ten
+    /// assert!(!list.contains(&10));
     /// ```
-    pub fn insert_after(&mut self, index: Index<T>, item: T) -> Option<Index<T>> {
-        // Get the current index
-        let (_prev_index, index, next_index) = match self.contents.get(index.index)? {
-            Entry::Free { .. } => return None,
-            Entry::Occupied(e) => {
-                // are we of the right generation?
-                if index.generation != e.generation {
-                    return None;
-                }
-                (e.prev, index.index, e.next)
-            }
-        };
-        let entry = Entry::Occupied(OccupiedEntry {
-            item,
-            generation: self.generation,
-            next: next_index,
-            prev: Some(index),
-        });
-        // Insert the item
-        let position = if let Some(position) = self.next_free {
-            // update next_free
-            match self.contents[position] {
-                Entry::Occupied { .. } => panic!("Corrupted list"),
-                Entry::Free { next_free } => self.next_free = next_free,
-            }
-            self.contents[position] = entry;
-            position
-        } else {
-            // we don't have any, so append to the end of the list
-            let position = self.contents.len();
-            self.contents.push(entry);
-            position
-        };
-        match &mut self.contents[index] {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => {
-                e.next = Some(position);
-            }
-        }
-        // Now, we need to update the prev node, if there was one, as well as
-        // the head, if there wasn't
-        match next_index {
-            Some(index) => match &mut self.contents[index] {
-                Entry::Occupied(e) => {
-                    e.prev = Some(position);
-                }
-                _ => panic!("Corrupted list"),
-            },
-            None => {
-                self.tail = Some(position);
-            }
-        }
-        Some(Index::new(position, self.generation))
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|e| e == value)
     }
 
-    /// Returns an iterator of references to the items in the list.
+    /// Returns the item at this index if it exists.
+    ///
+    /// If there's an item at this index, then this will return a reference to
+    /// it. If not, returns `None`.
+    ///
+    /// Indexes are generational, and so this method will use the generation to
+    /// determine if this element exists. For more, see [`Index`'s documentation].
+    ///
+    /// [`Index`'s documentation]: struct.Index.html
     ///
     /// # Examples
     ///
-    /// Using an iterator to print out all of the items in a list:
+    /// Getting an element at an index:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// list.push_back(5);
-    /// list.push_back(10);
-    /// list.push_back(15);
+    /// let five = list.push_back(5);
     ///
-    /// // This prints 5, 10, and then 15, each on its own line
-    /// for element in list.iter() {
-    ///     println!("{}", element);
-    /// }
+    /// assert_eq!(list.get(five), Some(&5));
     /// ```
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        Iter {
-            list: self,
-            next_index: self.head,
-        }
-    }
-
-    /// Returns an `Index` to this item.
     ///
-    /// If this item is not in the list, returns `None`.
+    /// An element that doesn't exist returns `None`:
     ///
-    /// # Examples
+    /// ```
+    /// use indexlist::IndexList;
     ///
-    /// Finding an item:
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert!(list.get(five).is_none());
+    /// ```
+    ///
+    /// Generational indexes ensure that we don't access incorrect items:
     ///
     /// ```
     /// use indexlist::IndexList;
@@ -1042,925 +1415,7842 @@ where
     /// let mut list = IndexList::new();
     ///
     /// let five = list.push_back(5);
+    /// list.push_back(10);
     ///
-    /// let index = list.index_of(&5);
+    /// list.remove(five);
     ///
-    /// assert_eq!(Some(five), index);
+    /// // since we have a free spot, this will go where 5 was
+    /// list.push_back(15);
+    ///
+    /// // our index is out of date, and so will not return 15 here
+    /// assert!(list.get(five).is_none());
     /// ```
-    pub fn index_of(&self, item: &T) -> Option<Index<T>> {
-        let mut next = self.head;
-
-        // iterate through entries from the front of the list
-        while let Some(index) = next {
-            // this should always be occupied because the index comes from a previous list items `next` field
-            let ref entry = match &self.contents[index] {
-                Entry::Free { .. } => panic!("Corrupt list"),
-                Entry::Occupied(entry) => entry,
-            };
-            // if we find the item, return the index, otherwise check the next list item
-            if &entry.item == item {
-                return Some(Index::new(index, entry.generation));
-            } else {
-                next = entry.next;
-            }
+    pub fn get(&self, index: Index<T>) -> Option<&T> {
+        match self.contents.get(index.index)? {
+            Entry::Occupied(e) if e.generation == index.generation => Some(&e.item),
+            _ => None,
         }
-
-        None
     }
 
-    /// Removes the head of the list.
+    /// Returns the item at this index if it exists.
     ///
-    /// If an item was removed, this will also return it.
+    /// If there's an item at this index, then this will return a mutable reference to
+    /// it. If not, returns `None`.
     ///
-    /// If this list is empty, returns `None`.
+    /// Indexes are generational, and so this method will use the generation to
+    /// determine if this element exists. For more, see [`Index`'s documentation].
+    ///
+    /// [`Index`'s documentation]: struct.Index.html
     ///
     /// # Examples
     ///
-    /// Removing the head:
+    /// Getting an element at an index:
     ///
     /// ```
     /// use indexlist::IndexList;
     ///
     /// let mut list = IndexList::new();
     ///
-    /// list.push_back(5);
+    /// let five = list.push_back(5);
     ///
-    /// assert_eq!(list.pop_front(), Some(5));
+    /// assert_eq!(list.get_mut(five), Some(&mut 5));
+    /// ```
+    ///
+    /// An element that doesn't exist returns `None`:
     ///
-    /// assert_eq!(list.iter().count(), 0);
     /// ```
-    pub fn pop_front(&mut self) -> Option<T> {
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert!(list.get_mut(five).is_none());
+    /// ```
+    ///
+    /// Generational indexes ensure that we don't access incorrect items:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// list.remove(five);
+    ///
+    /// // since we have a free spot, this will go where 5 was
+    /// list.push_back(15);
+    ///
+    /// // our index is out of date, and so will not return 15 here
+    /// assert!(list.get_mut(five).is_none());
+    /// ```
+    pub fn get_mut(&mut self, index: Index<T>) -> Option<&mut T> {
+        match self.contents.get_mut(index.index)? {
+            Entry::Occupied(e) if e.generation == index.generation => Some(&mut e.item),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of the element referred to by this index, if it exists.
+    ///
+    /// Since you get back an owned value, the list isn't borrowed afterwards.
+    ///
+    /// # Examples
+    ///
+    /// Getting a copy of an element, then changing the list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(String::from("five"));
+    ///
+    /// let copy = list.get_cloned(five).unwrap();
+    /// list.remove(five);
+    ///
+    /// assert_eq!(copy, "five");
+    /// assert_eq!(list.get_cloned(five), None);
+    /// ```
+    pub fn get_cloned(&self, index: Index<T>) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get(index).cloned()
+    }
+
+    pub fn next_index(&self, index: Index<T>) -> Option<Index<T>> {
+        match self.contents.get(index.index)? {
+            Entry::Occupied(e) if e.generation == index.generation => {
+                match self.live_forward(e.next) {
+                    Some(index) => match self.contents.get(index)? {
+                        Entry::Occupied(e) => Some(Index::new(index, e.generation)),
+                        _ => panic!("Corrupted list"),
+                    },
+                    _ => None, // this element was at the end of the list
+                }
+            }
+            _ => None, // this was an invalid or outdated index
+        }
+    }
+
+    pub fn prev_index(&self, index: Index<T>) -> Option<Index<T>> {
+        match self.contents.get(index.index)? {
+            Entry::Occupied(e) if e.generation == index.generation => {
+                match self.live_backward(e.prev) {
+                    Some(index) => match self.contents.get(index)? {
+                        Entry::Occupied(e) => Some(Index::new(index, e.generation)),
+                        _ => panic!("Corrupted list"),
+                    },
+                    _ => None, // this element was at the end of the list
+                }
+            }
+            _ => None, // this was an invalid or outdated index
+        }
+    }
+
+    /// Removes the item at this index, and returns the removed item.
+    ///
+    /// If there's an item at this index, then this will remove it from the list
+    /// and return it. If there isn't, it will return `None`.
+    ///
+    /// Indexes are generational, and so this method will use the generation to
+    /// determine if this element exists. For more, see [`Index`'s documentation].
+    ///
+    /// [`Index`'s documentation]: struct.Index.html
+    ///
+    /// # Examples
+    ///
+    /// Removing an element from an index:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let five = list.remove(five);
+    /// assert_eq!(five, Some(5));
+    ///
+    /// assert!(!list.contains(&5));
+    /// ```
+    ///
+    /// An element that doesn't exist returns `None`:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert!(list.remove(five).is_none());
+    /// ```
+    ///
+    /// Generational indexes ensure that we don't access incorrect items:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// list.remove(five);
+    ///
+    /// // since we have a free spot, this will go where 5 was
+    /// list.push_back(15);
+    ///
+    /// // our index is out of date, and so will not return 15 here
+    /// assert!(list.remove(five).is_none());
+    /// ```
+    pub fn remove(&mut self, index: Index<T>) -> Option<T> {
+        self.sweep();
+
+        match self.contents.get(index.index)? {
+            Entry::Free { .. } | Entry::Tombstone { .. } => return None,
+            Entry::Occupied(e) => {
+                // are we of the right generation?
+                if index.generation != e.generation {
+                    return None;
+                }
+            }
+        }
+
+        let item = self.unlink(index.index);
+
+        self.trim_if_auto();
+
+        Some(item)
+    }
+
+    // takes the item in this occupied slot out of the list, fixes up the links
+    // on either side of it, and puts the slot on the free list
+    fn unlink(&mut self, slot: usize) -> T {
+        self.detach(slot);
+
+        let removed = std::mem::replace(&mut self.contents[slot], Entry::Free { next_free: None });
+
+        let e = match removed {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => e,
+        };
+
+        // update our free list to include this new space
+        self.free_slot(slot);
+
+        // when we remove a node, we need to increase the generation to invalidate
+        // older indexes that may be refering to this spot
+        self.generation += 1;
+        self.len -= 1;
+
+        e.item
+    }
+
+    // takes the node in this occupied slot out of the chain of links, without
+    // touching the node itself
+    fn detach(&mut self, slot: usize) {
+        let (prev, next) = {
+            let e = self.occupied(slot);
+            (e.prev, e.next)
+        };
+
+        // whatever was before us now points at whatever was after us, and if
+        // there's nothing on one side, we were the head or the tail
+        match prev {
+            Some(prev) => self.occupied_mut(prev).next = next,
+            None => self.head = next,
+        }
+
+        match next {
+            Some(next) => self.occupied_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    // puts this slot on the free list, at the front or the back depending on
+    // the free strategy
+    fn free_slot(&mut self, slot: usize) {
+        match self.free_strategy {
+            FreeStrategy::Lifo => {
+                self.contents[slot] = Entry::Free {
+                    next_free: self.next_free,
+                };
+
+                if self.next_free.is_none() {
+                    self.free_tail = Some(slot);
+                }
+
+                self.next_free = Some(slot);
+            }
+            FreeStrategy::Fifo => {
+                self.contents[slot] = Entry::Free { next_free: None };
+
+                match self.free_tail {
+                    Some(tail) => {
+                        self.contents[tail] = Entry::Free {
+                            next_free: Some(slot),
+                        }
+                    }
+                    None => self.next_free = Some(slot),
+                }
+
+                self.free_tail = Some(slot);
+            }
+        }
+    }
+
+    // takes the first slot off of the free list, if there is one
+    fn take_free(&mut self) -> Option<usize> {
+        let slot = self.next_free?;
+
+        match self.contents[slot] {
+            Entry::Occupied { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Free { next_free } => self.next_free = next_free,
+        }
+
+        if self.next_free.is_none() {
+            self.free_tail = None;
+        }
+
+        Some(slot)
+    }
+
+    // links the detached node in `slot` in just before the node in `target`
+    fn attach_before(&mut self, slot: usize, target: usize) {
+        let prev = self.occupied(target).prev;
+
+        {
+            let e = self.occupied_mut(slot);
+            e.prev = prev;
+            e.next = Some(target);
+        }
+
+        self.occupied_mut(target).prev = Some(slot);
+
+        match prev {
+            Some(prev) => self.occupied_mut(prev).next = Some(slot),
+            None => self.head = Some(slot),
+        }
+    }
+
+    // links the detached node in `slot` in just after the node in `target`
+    fn attach_after(&mut self, slot: usize, target: usize) {
+        let next = self.occupied(target).next;
+
+        {
+            let e = self.occupied_mut(slot);
+            e.prev = Some(target);
+            e.next = next;
+        }
+
+        self.occupied_mut(target).next = Some(slot);
+
+        match next {
+            Some(next) => self.occupied_mut(next).prev = Some(slot),
+            None => self.tail = Some(slot),
+        }
+    }
+
+    /// Removes the item at this position in the list, and returns the removed
+    /// item.
+    ///
+    /// Positions start at zero for the head of the list. If the list is too
+    /// short to have an item at this position, returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// Removing the second item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
+    /// list.push_back(15);
+    ///
+    /// assert_eq!(list.remove_at(1), Some(10));
+    ///
+    /// assert!(!list.contains(&10));
+    /// ```
+    ///
+    /// A position past the end returns `None`:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    ///
+    /// assert!(list.remove_at(1).is_none());
+    /// ```
+    pub fn remove_at(&mut self, n: usize) -> Option<T> {
+        let mut index = self.head_index()?;
+
+        for _ in 0..n {
+            index = self.next_index(index)?;
+        }
+
+        self.remove(index)
+    }
+
+    /// Inserts an element immediately before the provided index. Returns `None`
+    /// if the element at the provided index was removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.insert_before(five, 0);
+    ///
+    /// assert_eq!(list.head(), Some(&0));
+    /// ```
+    pub fn insert_before(&mut self, index: Index<T>, item: T) -> Option<Index<T>> {
+        self.sweep();
+
+        // Get the current index
+        let (prev_index, index, _next_index) = match self.contents.get(index.index)? {
+            Entry::Free { .. } | Entry::Tombstone { .. } => return None,
+            Entry::Occupied(e) => {
+                // are we of the right generation?
+                if index.generation != e.generation {
+                    return None;
+                }
+                (e.prev, index.index, e.next)
+            }
+        };
+        let entry = Entry::Occupied(OccupiedEntry {
+            item,
+            generation: self.generation,
+            next: Some(index),
+            prev: prev_index,
+        });
+        // Insert the item
+        let position = if let Some(position) = self.take_free() {
+            self.contents[position] = entry;
+            position
+        } else {
+            // we don't have any, so append to the end of the list
+            let position = self.contents.len();
+            self.contents.push(entry);
+            position
+        };
+        match &mut self.contents[index] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => {
+                e.prev = Some(position);
+            }
+        }
+        // Now, we need to update the prev node, if there was one, as well as
+        // the head, if there wasn't
+        match prev_index {
+            Some(index) => match &mut self.contents[index] {
+                Entry::Occupied(e) => {
+                    e.next = Some(position);
+                }
+                _ => panic!("Corrupted list"),
+            },
+            None => {
+                self.head = Some(position);
+            }
+        }
+        self.len += 1;
+        Some(Index::new(position, self.generation))
+    }
+
+    /// Inserts an element immediately after the provided index. Returns `None`
+    /// if the element at the provided index was removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.insert_after(five, 0);
+    ///
+    /// assert_eq!(list.tail_index().and_then(|tail| list.get(tail)), Some(&0));
+    /// ```
+    pub fn insert_after(&mut self, index: Index<T>, item: T) -> Option<Index<T>> {
+        self.sweep();
+
+        // Get the current index
+        let (_prev_index, index, next_index) = match self.contents.get(index.index)? {
+            Entry::Free { .. } | Entry::Tombstone { .. } => return None,
+            Entry::Occupied(e) => {
+                // are we of the right generation?
+                if index.generation != e.generation {
+                    return None;
+                }
+                (e.prev, index.index, e.next)
+            }
+        };
+        let entry = Entry::Occupied(OccupiedEntry {
+            item,
+            generation: self.generation,
+            next: next_index,
+            prev: Some(index),
+        });
+        // Insert the item
+        let position = if let Some(position) = self.take_free() {
+            self.contents[position] = entry;
+            position
+        } else {
+            // we don't have any, so append to the end of the list
+            let position = self.contents.len();
+            self.contents.push(entry);
+            position
+        };
+        match &mut self.contents[index] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => {
+                e.next = Some(position);
+            }
+        }
+        // Now, we need to update the prev node, if there was one, as well as
+        // the head, if there wasn't
+        match next_index {
+            Some(index) => match &mut self.contents[index] {
+                Entry::Occupied(e) => {
+                    e.prev = Some(position);
+                }
+                _ => panic!("Corrupted list"),
+            },
+            None => {
+                self.tail = Some(position);
+            }
+        }
+        self.len += 1;
+        Some(Index::new(position, self.generation))
+    }
+
+    /// Returns an iterator of references to the items in the list.
+    ///
+    /// # Examples
+    ///
+    /// Using an iterator to print out all of the items in a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
+    /// list.push_back(15);
+    ///
+    /// // This prints 5, 10, and then 15, each on its own line
+    /// for element in list.iter() {
+    ///     println!("{}", element);
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator that yields the head, and then every `step`-th
+    /// item after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// Taking every other item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// for i in 0..6 {
+    ///     list.push_back(i);
+    /// }
+    ///
+    /// let evens: Vec<_> = list.iter_stride(2).collect();
+    ///
+    /// assert_eq!(evens, vec![&0, &2, &4]);
+    /// ```
+    pub fn iter_stride(&self, step: usize) -> impl Iterator<Item = &T> {
+        assert!(step != 0, "iter_stride: step must be non-zero");
+
+        self.iter().step_by(step)
+    }
+
+    /// Returns an `Index` to this item.
+    ///
+    /// If this item is not in the list, returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// Finding an item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let index = list.index_of(&5);
+    ///
+    /// assert_eq!(Some(five), index);
+    /// ```
+    pub fn index_of(&self, item: &T) -> Option<Index<T>> {
+        let mut next = self.head;
+
+        // iterate through entries from the front of the list
+        while let Some(index) = next {
+            // this should always be occupied because the index comes from a previous list items `next` field
+            let entry = match &self.contents[index] {
+                Entry::Free { .. } => panic!("Corrupt list"),
+                Entry::Occupied(entry) => entry,
+                // this was lazily removed, so skip over it
+                Entry::Tombstone { next: after, .. } => {
+                    next = *after;
+                    continue;
+                }
+            };
+            // if we find the item, return the index, otherwise check the next list item
+            if &entry.item == item {
+                return Some(Index::new(index, entry.generation));
+            } else {
+                next = entry.next;
+            }
+        }
+
+        None
+    }
+
+    /// Removes the head of the list.
+    ///
+    /// If an item was removed, this will also return it.
+    ///
+    /// If this list is empty, returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// Removing the head:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    ///
+    /// assert_eq!(list.pop_front(), Some(5));
+    ///
+    /// assert_eq!(list.iter().count(), 0);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.sweep();
+
         // if we have no head, then we have an empty list, so return
         let head_index = self.head?;
 
-        // we want to do just get, but then we run into borrowing issues.
-        //
-        // we could implement Entry, but... ugh. So let's fetch just the indexes for now.
-        let (head_index, next_index) = match self.contents.get(head_index)? {
-            Entry::Free { .. } => return None,
-            Entry::Occupied(e) => (head_index, e.next),
-        };
+        let item = self.unlink(head_index);
+
+        self.trim_if_auto();
+
+        Some(item)
+    }
+
+    /// Compacts the list so that its items are stored densely, in list order,
+    /// and releases any unused space back to the allocator.
+    ///
+    /// Because the underlying storage is a sparse vector, removing items
+    /// leaves holes behind. This moves every item to the front of the vector,
+    /// in the same order you'd see from `iter`, and drops the free slots.
+    ///
+    /// This changes where items live, and so it invalidates every `Index` you
+    /// currently hold, even ones to items that are still in the list.
+    ///
+    /// # Examples
+    ///
+    /// Compacting a list with holes in it:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// let ten = list.push_back(10);
+    /// list.push_back(15);
+    ///
+    /// list.remove(ten);
+    ///
+    /// list.compact();
+    ///
+    /// // the items are all still there, in the same order
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&5, &15]);
+    ///
+    /// // but our old index no longer works
+    /// assert!(list.get(five).is_none());
+    /// ```
+    pub fn compact(&mut self) {
+        self.sweep();
+
+        // every item is about to move, so we bump the generation and give
+        // every item the new one; this makes any index from before stale
+        self.generation += 1;
+
+        let mut contents = Vec::new();
+        let mut next = self.head;
+
+        while let Some(index) = next {
+            let entry =
+                std::mem::replace(&mut self.contents[index], Entry::Free { next_free: None });
+
+            let mut entry = match entry {
+                Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+                Entry::Occupied(e) => e,
+            };
+
+            next = entry.next;
+
+            let position = contents.len();
+
+            entry.generation = self.generation;
+            entry.prev = position.checked_sub(1);
+            entry.next = None;
+
+            // fix up the item before us to point at our new position
+            if let Some(prev) = entry.prev {
+                match &mut contents[prev] {
+                    Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+                    Entry::Occupied(e) => e.next = Some(position),
+                }
+            }
+
+            contents.push(Entry::Occupied(entry));
+        }
+
+        contents.shrink_to_fit();
+
+        self.head = if contents.is_empty() { None } else { Some(0) };
+        self.tail = contents.len().checked_sub(1);
+        self.next_free = None;
+        self.free_tail = None;
+        self.contents = contents;
+    }
+
+    /// Compacts the list, and then returns references to all of its items,
+    /// in list order.
+    ///
+    /// The underlying storage holds each item wrapped up with its links, so
+    /// even once it's dense it can't be borrowed as a `&[T]`. Instead, this
+    /// gives you a `Vec` of references, which you can use as a `&[&T]`.
+    ///
+    /// Like `compact`, this moves items around, and so it invalidates every
+    /// `Index` you currently hold.
+    ///
+    /// # Examples
+    ///
+    /// Looking at a list with holes in it:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[5, 10, 15]);
+    ///
+    /// let ten = list.index_of(&10).unwrap();
+    /// list.remove(ten);
+    ///
+    /// assert_eq!(list.as_slice(), [&5, &15]);
+    /// ```
+    pub fn as_slice(&mut self) -> Vec<&T> {
+        self.compact();
+
+        // after compacting, storage order is list order
+        self.contents
+            .iter()
+            .map(|entry| match entry {
+                Entry::Occupied(e) => &e.item,
+                Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            })
+            .collect()
+    }
+
+    /// Returns the fraction of slots in the underlying vector that are free.
+    ///
+    /// This is `0.0` for a list with no holes in it, and approaches `1.0` as
+    /// more of the list is removed. An empty list with no slots at all also
+    /// returns `0.0`.
+    ///
+    /// # Examples
+    ///
+    /// Checking how much of the list is free:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// assert_eq!(list.free_ratio(), 0.0);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert_eq!(list.free_ratio(), 0.5);
+    /// ```
+    pub fn free_ratio(&self) -> f64 {
+        if self.contents.is_empty() {
+            return 0.0;
+        }
+
+        self.free_slots() as f64 / self.contents.len() as f64
+    }
+
+    /// Removes any free slots from the end of the underlying vector.
+    ///
+    /// Unlike `compact`, this never moves an item, and so every `Index`
+    /// you hold stays valid. The flip side is that it can only reclaim free
+    /// slots that come after the last item in the vector; holes in the middle
+    /// stay where they are.
+    ///
+    /// # Examples
+    ///
+    /// Trimming after removing the last item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// let ten = list.push_back(10);
+    ///
+    /// list.remove(ten);
+    ///
+    /// list.trim_trailing_free();
+    ///
+    /// assert_eq!(list.free_ratio(), 0.0);
+    ///
+    /// // our index to five still works
+    /// assert_eq!(list.get(five), Some(&5));
+    /// ```
+    pub fn trim_trailing_free(&mut self) {
+        self.sweep();
+
+        let len = self
+            .contents
+            .iter()
+            .rposition(|e| match e {
+                Entry::Free { .. } => false,
+                Entry::Occupied(_) | Entry::Tombstone { .. } => true,
+            })
+            .map_or(0, |index| index + 1);
+
+        if len == self.contents.len() {
+            return;
+        }
+
+        // the slots we're about to drop are on the free list, so we need to
+        // relink it to skip over them, keeping the rest in the same order
+        let mut next = self.next_free;
+        let mut last_kept = None;
+
+        self.next_free = None;
+
+        while let Some(index) = next {
+            next = match self.contents[index] {
+                Entry::Occupied { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+                Entry::Free { next_free } => next_free,
+            };
+
+            if index < len {
+                match last_kept {
+                    Some(last) => {
+                        self.contents[last] = Entry::Free {
+                            next_free: Some(index),
+                        }
+                    }
+                    None => self.next_free = Some(index),
+                }
+
+                last_kept = Some(index);
+            }
+        }
+
+        if let Some(last) = last_kept {
+            self.contents[last] = Entry::Free { next_free: None };
+        }
+
+        self.free_tail = last_kept;
+
+        self.contents.truncate(len);
+        self.contents.shrink_to_fit();
+    }
+
+    /// Removes items from the head of the list for as long as they match a
+    /// predicate.
+    ///
+    /// This stops at the first item that doesn't match, and returns the
+    /// removed items in the order they were in the list.
+    ///
+    /// # Examples
+    ///
+    /// Removing leading even numbers:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(2);
+    /// list.push_back(4);
+    /// list.push_back(5);
+    /// list.push_back(6);
+    ///
+    /// let evens = list.pop_front_while(|n| n % 2 == 0);
+    ///
+    /// assert_eq!(evens, vec![2, 4]);
+    /// assert_eq!(list.head(), Some(&5));
+    /// ```
+    pub fn pop_front_while<F>(&mut self, mut pred: F) -> Vec<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut popped = Vec::new();
+
+        while self.head().is_some_and(&mut pred) {
+            // we just checked that there's a head
+            popped.push(self.pop_front().unwrap());
+        }
+
+        popped
+    }
+
+    /// Removes items from the tail of the list for as long as they match a
+    /// predicate.
+    ///
+    /// This stops at the first item that doesn't match, and returns the
+    /// removed items in the order they were removed, which is the reverse of
+    /// the order they were in the list.
+    ///
+    /// # Examples
+    ///
+    /// Removing trailing large numbers:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// list.push_back(8);
+    /// list.push_back(9);
+    ///
+    /// let large = list.pop_back_while(|&n| n > 5);
+    ///
+    /// assert_eq!(large, vec![9, 8]);
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    pub fn pop_back_while<F>(&mut self, mut pred: F) -> Vec<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut popped = Vec::new();
+
+        while let Some(tail) = self.tail_index() {
+            if !pred(&self[tail]) {
+                break;
+            }
+
+            popped.push(self.remove(tail).unwrap());
+        }
+
+        popped
+    }
+
+    /// Returns an iterator that goes through the list from head to tail, and
+    /// then starts over again at the head, forever.
+    ///
+    /// If the list is empty, the iterator doesn't yield anything, rather than
+    /// looping forever.
+    ///
+    /// # Examples
+    ///
+    /// Going around the list more than once:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// list.push_back(2);
+    ///
+    /// let items: Vec<_> = list.cycle().take(5).collect();
+    ///
+    /// assert_eq!(items, vec![&1, &2, &1, &2, &1]);
+    /// ```
+    pub fn cycle(&self) -> impl Iterator<Item = &T> {
+        Iter::new(self).cycle()
+    }
+
+    /// Replaces a range of the list with new items.
+    ///
+    /// This removes every item from `from` up to, but not including, `to`,
+    /// and then inserts the items from `replacement` in their place, in order.
+    /// If `to` is `None`, the range goes all the way to the end of the list.
+    /// The removed items are returned, in list order.
+    ///
+    /// If `from` isn't in the list, or `to` doesn't come after `from`, the
+    /// list is left alone and an empty `Vec` is returned.
+    ///
+    /// # Examples
+    ///
+    /// Replacing the middle of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// let two = list.push_back(2);
+    /// list.push_back(3);
+    /// let four = list.push_back(4);
+    ///
+    /// let removed = list.splice(two, Some(four), vec![9]);
+    ///
+    /// assert_eq!(removed, vec![2, 3]);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &9, &4]);
+    /// ```
+    ///
+    /// Replacing everything up to the end:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// let two = list.push_back(2);
+    /// list.push_back(3);
+    ///
+    /// let removed = list.splice(two, None, vec![8, 9]);
+    ///
+    /// assert_eq!(removed, vec![2, 3]);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &8, &9]);
+    /// ```
+    pub fn splice<I>(&mut self, from: Index<T>, to: Option<Index<T>>, replacement: I) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if self.get(from).is_none() {
+            return Vec::new();
+        }
+
+        // find everything in the range before touching anything, so that a
+        // bad `to` doesn't leave us with a half-finished splice
+        let mut range = Vec::new();
+        let mut next = Some(from);
+
+        while next != to {
+            match next {
+                Some(index) => {
+                    range.push(index);
+                    next = self.next_index(index);
+                }
+                // we ran off the end without finding `to`
+                None => return Vec::new(),
+            }
+        }
+
+        let removed = range
+            .into_iter()
+            .map(|index| self.remove(index).unwrap())
+            .collect();
+
+        match to {
+            Some(to) => {
+                for item in replacement {
+                    self.insert_before(to, item);
+                }
+            }
+            None => {
+                for item in replacement {
+                    self.push_back(item);
+                }
+            }
+        }
+
+        removed
+    }
+
+    /// Returns a copy of the item at this index, or the default value if it
+    /// doesn't exist.
+    ///
+    /// Unlike indexing with `list[index]`, this never panics on a stale index.
+    ///
+    /// # Examples
+    ///
+    /// Getting an item, and then a default:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// assert_eq!(list.get_or_default(five), 5);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert_eq!(list.get_or_default(five), 0);
+    /// ```
+    pub fn get_or_default(&self, index: Index<T>) -> T
+    where
+        T: Default + Clone,
+    {
+        self.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Returns an iterator of references to the items in the list, along with
+    /// the slot each one is stored in.
+    ///
+    /// A slot is the item's position in the underlying vector, not its
+    /// position in the list. Since removed slots get reused, the two can be
+    /// quite different. This is mostly useful for debugging.
+    ///
+    /// # Examples
+    ///
+    /// A reused slot shows up at the end of the list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// list.remove(five);
+    /// list.push_back(15);
+    ///
+    /// let slots: Vec<_> = list.iter_slots().collect();
+    ///
+    /// assert_eq!(slots, vec![(1, &10), (0, &15)]);
+    /// ```
+    pub fn iter_slots(&self) -> impl Iterator<Item = (usize, &T)> {
+        let mut iter = Iter::new(self);
+
+        std::iter::from_fn(move || iter.next_entry().map(|(slot, e)| (slot, &e.item)))
+    }
+
+    /// Returns an iterator of mutable references to the items in the list,
+    /// along with each item's `Index`.
+    ///
+    /// # Examples
+    ///
+    /// Doubling every item, and remembering where the big ones are:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
+    /// list.push_back(15);
+    ///
+    /// let mut big = Vec::new();
+    ///
+    /// for (index, item) in list.iter_mut_indexed() {
+    ///     *item *= 2;
+    ///
+    ///     if *item > 15 {
+    ///         big.push(index);
+    ///     }
+    /// }
+    ///
+    /// for index in big {
+    ///     list.remove(index);
+    /// }
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10]);
+    /// ```
+    pub fn iter_mut_indexed(&mut self) -> impl Iterator<Item = (Index<T>, &mut T)> {
+        let order: Vec<usize> = self.iter_slots().map(|(slot, _)| slot).collect();
+
+        // grab a mutable reference to every occupied slot, so we can hand
+        // them out in list order
+        let mut entries: Vec<Option<&mut OccupiedEntry<T>>> = self
+            .contents
+            .iter_mut()
+            .map(|e| match e {
+                Entry::Occupied(e) => Some(e),
+                Entry::Free { .. } | Entry::Tombstone { .. } => None,
+            })
+            .collect();
+
+        order.into_iter().map(move |slot| {
+            // each slot shows up in the list once, and it's occupied
+            let e = entries[slot].take().expect("Corrupted list");
+
+            (Index::new(slot, e.generation), &mut e.item)
+        })
+    }
+
+    /// Moves all of the items in `other` to the front of this list, in order,
+    /// leaving `other` empty.
+    ///
+    /// The items get new indexes in this list, and any indexes you held into
+    /// `other` are no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// Prepending one list to another:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    /// list.push_back(3);
+    /// list.push_back(4);
+    ///
+    /// let mut other = IndexList::new();
+    /// other.push_back(1);
+    /// other.push_back(2);
+    ///
+    /// list.prepend(&mut other);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    /// assert!(other.head().is_none());
+    /// ```
+    pub fn prepend(&mut self, other: &mut IndexList<T>) {
+        let items = other.take();
+
+        match self.head_index() {
+            Some(head) => {
+                for item in items {
+                    self.insert_before(head, item);
+                }
+            }
+            None => {
+                for item in items {
+                    self.push_back(item);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of free slots in the underlying vector.
+    ///
+    /// Free slots are left behind when items are removed, and are reused by
+    /// later insertions before the vector grows.
+    ///
+    /// # Examples
+    ///
+    /// Removing an item frees its slot:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// assert_eq!(list.free_slots(), 0);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert_eq!(list.free_slots(), 1);
+    /// ```
+    pub fn free_slots(&self) -> usize {
+        self.contents.len() - self.len - self.tombstones
+    }
+
+    /// Returns the number of occupied slots in the underlying vector.
+    ///
+    /// Every item takes up exactly one slot, so this is the same as `len`.
+    ///
+    /// # Examples
+    ///
+    /// Removing an item frees its slot:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert_eq!(list.occupied_slots(), 1);
+    /// ```
+    pub fn occupied_slots(&self) -> usize {
+        self.len
+    }
+
+    /// Removes the tail of the list.
+    ///
+    /// If an item was removed, this will also return it.
+    ///
+    /// If this list is empty, returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// Removing the tail:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// assert_eq!(list.pop_back(), Some(10));
+    ///
+    /// assert_eq!(list.iter().count(), 1);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.sweep();
+
+        // if we have no tail, then we have an empty list, so return
+        let tail_index = self.tail?;
+
+        let item = self.unlink(tail_index);
+
+        self.trim_if_auto();
+
+        Some(item)
+    }
+
+    // called after every removal; trims the list if it's set up to do so
+    fn trim_if_auto(&mut self) {
+        if !self.auto_trim {
+            return;
+        }
+
+        self.removals_since_trim += 1;
+
+        // trimming walks the whole free list, so unless the list is now empty,
+        // we wait for a removal for every four slots before doing it again;
+        // that way each removal only pays a constant amount on average
+        let due = self.len == 0 || self.removals_since_trim * 4 >= self.contents.len();
+
+        if due && self.free_ratio() > 0.5 {
+            self.removals_since_trim = 0;
+            self.trim_trailing_free();
+        }
+    }
+
+    /// Returns mutable references to the item at this index and the item
+    /// after it.
+    ///
+    /// Returns `None` if there's no item at this index, or if it's the tail of
+    /// the list and so has nothing after it.
+    ///
+    /// # Examples
+    ///
+    /// Averaging two neighbors:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let ten = list.push_back(10);
+    /// list.push_back(20);
+    ///
+    /// if let Some((a, b)) = list.get_pair_mut(ten) {
+    ///     let average = (*a + *b) / 2;
+    ///     *a = average;
+    ///     *b = average;
+    /// }
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&15, &15]);
+    /// ```
+    pub fn get_pair_mut(&mut self, index: Index<T>) -> Option<(&mut T, &mut T)> {
+        let next = self.next_index(index)?;
+
+        match get_two_mut(&mut self.contents, index.index, next.index) {
+            (Entry::Occupied(first), Entry::Occupied(second)) => {
+                Some((&mut first.item, &mut second.item))
+            }
+            _ => panic!("Corrupted list"),
+        }
+    }
+
+    /// Adds these items to the head of the list, keeping them in the order
+    /// they're given in.
+    ///
+    /// Calling `push_front` in a loop reverses the items, since each one ends
+    /// up in front of the last. With this, the first item yielded ends up as
+    /// the new head. Returns the indexes of the new items, in order.
+    ///
+    /// # Examples
+    ///
+    /// Putting several numbers in front of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(4);
+    /// list.push_back(5);
+    ///
+    /// list.push_front_all_ordered(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+    /// ```
+    pub fn push_front_all_ordered<I>(&mut self, items: I) -> Vec<Index<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        match self.head_index() {
+            // the old head is always valid, so we can unwrap
+            Some(head) => items
+                .into_iter()
+                .map(|item| self.insert_before(head, item).unwrap())
+                .collect(),
+            None => items.into_iter().map(|item| self.push_back(item)).collect(),
+        }
+    }
+
+    /// Returns the `Index` of every occurrence of this item, in list order.
+    ///
+    /// If the item isn't in the list, the returned `Vec` is empty.
+    ///
+    /// # Examples
+    ///
+    /// Finding each copy of an item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let first = list.push_back(1);
+    /// list.push_back(2);
+    /// let second = list.push_back(1);
+    ///
+    /// assert_eq!(list.all_indexes_of(&1), vec![first, second]);
+    /// ```
+    pub fn all_indexes_of(&self, item: &T) -> Vec<Index<T>> {
+        self.iter_with_indexes()
+            .filter(|&(_, e)| e == item)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    // like `iter`, but also yields the index of each item
+    fn iter_with_indexes(&self) -> impl Iterator<Item = (Index<T>, &T)> {
+        let mut iter = Iter::new(self);
+
+        std::iter::from_fn(move || {
+            iter.next_entry()
+                .map(|(slot, e)| (Index::new(slot, e.generation), &e.item))
+        })
+    }
+
+    /// Swaps the item at this index with the item after it.
+    ///
+    /// The items are relinked rather than moved, so every `Index` stays valid
+    /// and keeps referring to the same item. Returns `false` if there's no item
+    /// at this index, or if it's the tail of the list.
+    ///
+    /// # Examples
+    ///
+    /// Moving an item down the list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let one = list.push_back(1);
+    /// list.push_back(2);
+    /// list.push_back(3);
+    ///
+    /// assert!(list.swap_with_next(one));
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &1, &3]);
+    /// assert_eq!(list.get(one), Some(&1));
+    /// ```
+    pub fn swap_with_next(&mut self, index: Index<T>) -> bool {
+        self.sweep();
+
+        let next = match self.next_index(index) {
+            Some(next) => next.index,
+            None => return false,
+        };
+        let index = index.index;
+
+        // we go from prev <-> index <-> next <-> after
+        // to prev <-> next <-> index <-> after
+        let prev = self.occupied(index).prev;
+        let after = self.occupied(next).next;
+
+        {
+            let entry = self.occupied_mut(index);
+            entry.prev = Some(next);
+            entry.next = after;
+        }
+
+        {
+            let entry = self.occupied_mut(next);
+            entry.prev = prev;
+            entry.next = Some(index);
+        }
+
+        match prev {
+            Some(prev) => self.occupied_mut(prev).next = Some(next),
+            None => self.head = Some(next),
+        }
+
+        match after {
+            Some(after) => self.occupied_mut(after).prev = Some(index),
+            None => self.tail = Some(index),
+        }
+
+        true
+    }
+
+    /// Swaps the item at this index with the item before it.
+    ///
+    /// The items are relinked rather than moved, so every `Index` stays valid
+    /// and keeps referring to the same item. Returns `false` if there's no item
+    /// at this index, or if it's the head of the list.
+    ///
+    /// # Examples
+    ///
+    /// Moving an item up the list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// let three = list.push_back(3);
+    ///
+    /// assert!(list.swap_with_prev(three));
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3, &2]);
+    /// assert_eq!(list.get(three), Some(&3));
+    /// ```
+    pub fn swap_with_prev(&mut self, index: Index<T>) -> bool {
+        match self.prev_index(index) {
+            Some(prev) => self.swap_with_next(prev),
+            None => false,
+        }
+    }
+
+    // returns the entry in this slot, which the caller knows to be occupied
+    fn occupied(&self, slot: usize) -> &OccupiedEntry<T> {
+        match &self.contents[slot] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => e,
+        }
+    }
+
+    // returns the entry in this slot, which the caller knows to be occupied
+    fn occupied_mut(&mut self, slot: usize) -> &mut OccupiedEntry<T> {
+        match &mut self.contents[slot] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => e,
+        }
+    }
+
+    /// Removes every item for which the closure returns true, and returns
+    /// them in list order.
+    ///
+    /// The closure is given each item's `Index` as well as the item itself, so
+    /// you can decide based on either.
+    ///
+    /// # Examples
+    ///
+    /// Removing items by index:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// let two = list.push_back(2);
+    /// list.push_back(3);
+    ///
+    /// let drained = list.drain_if_indexed(|index, _| index == two);
+    ///
+    /// assert_eq!(drained, vec![2]);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3]);
+    /// ```
+    pub fn drain_if_indexed<F>(&mut self, mut f: F) -> Vec<T>
+    where
+        F: FnMut(Index<T>, &T) -> bool,
+    {
+        let mut drained = Vec::new();
+        let mut next = self.head_index();
+
+        while let Some(index) = next {
+            // grab this first, since we might remove `index`
+            next = self.next_index(index);
+
+            // we got this index from the list itself, so we can unwrap
+            if f(index, self.get(index).unwrap()) {
+                drained.push(self.remove(index).unwrap());
+            }
+        }
+
+        drained
+    }
+
+    /// Adds this item to the tail of the list, but only if there's a free slot
+    /// to put it in.
+    ///
+    /// This never grows the underlying vector. If there's no free slot, the
+    /// item is handed back in the `Err`.
+    ///
+    /// # Examples
+    ///
+    /// Reusing a slot, and then running out:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.pop_front();
+    ///
+    /// assert!(list.try_push_back_in_place(10).is_ok());
+    /// assert_eq!(list.try_push_back_in_place(15), Err(15));
+    /// ```
+    pub fn try_push_back_in_place(&mut self, item: T) -> Result<Index<T>, T> {
+        // tombstones only turn into free slots once they're swept
+        self.sweep();
+
+        if self.next_free.is_none() {
+            return Err(item);
+        }
+
+        Ok(self.push_back(item))
+    }
+
+    /// Returns an iterator of copies of the items in the list.
+    ///
+    /// # Examples
+    ///
+    /// Collecting the items into a `Vec`:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// let items: Vec<i32> = list.iter_copied().collect();
+    ///
+    /// assert_eq!(items, vec![5, 10]);
+    /// ```
+    pub fn iter_copied(&self) -> impl Iterator<Item = T> + '_
+    where
+        T: Copy,
+    {
+        self.iter().copied()
+    }
+
+    /// Removes the item at this index, and returns the removed item, but
+    /// leaves fixing up the list until later.
+    ///
+    /// A normal `remove` relinks the items on either side of the removed one
+    /// straight away. This leaves a tombstone behind instead, which iteration
+    /// and the other accessors skip over, and is cleaned up by `sweep`. If
+    /// you're removing a lot of items in one go, this can be faster.
+    ///
+    /// Any method that changes the structure of the list, like `push_back` or
+    /// `remove`, will `sweep` first, so you don't have to remember to.
+    ///
+    /// The index is invalidated immediately, just like with `remove`.
+    ///
+    /// # Examples
+    ///
+    /// Removing several items, and then cleaning up:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// let ten = list.push_back(10);
+    /// list.push_back(15);
+    ///
+    /// assert_eq!(list.remove_lazy(five), Some(5));
+    /// assert_eq!(list.remove_lazy(ten), Some(10));
+    ///
+    /// // the removed items are already gone
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&15]);
+    /// assert!(list.get(five).is_none());
+    ///
+    /// list.sweep();
+    ///
+    /// assert_eq!(list.free_slots(), 2);
+    /// ```
+    pub fn remove_lazy(&mut self, index: Index<T>) -> Option<T> {
+        let (next, prev) = match self.contents.get(index.index)? {
+            Entry::Occupied(e) if e.generation == index.generation => (e.next, e.prev),
+            _ => return None,
+        };
+
+        let removed = std::mem::replace(
+            &mut self.contents[index.index],
+            Entry::Tombstone { next, prev },
+        );
+
+        // we still invalidate the index right away, as in `remove`
+        self.generation += 1;
+        self.len -= 1;
+        self.tombstones += 1;
+
+        match removed {
+            Entry::Occupied(e) => Some(e.item),
+            _ => panic!("Corrupted list"),
+        }
+    }
+
+    /// Cleans up after `remove_lazy`.
+    ///
+    /// This walks the list once, unlinking every tombstone left behind by
+    /// `remove_lazy` and adding its slot to the free list. If there aren't any
+    /// tombstones, it does nothing.
+    ///
+    /// # Examples
+    ///
+    /// Sweeping after lazy removal:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// list.remove_lazy(five);
+    ///
+    /// assert_eq!(list.free_slots(), 0);
+    ///
+    /// list.sweep();
+    ///
+    /// assert_eq!(list.free_slots(), 1);
+    /// ```
+    pub fn sweep(&mut self) {
+        if self.tombstones == 0 {
+            return;
+        }
+
+        let mut next = self.head;
+        let mut last_live = None;
+
+        self.head = None;
+
+        while let Some(index) = next {
+            let (after, live) = match &mut self.contents[index] {
+                Entry::Free { .. } => panic!("Corrupted list"),
+                Entry::Tombstone { next, .. } => (*next, false),
+                Entry::Occupied(e) => {
+                    e.prev = last_live;
+                    (e.next, true)
+                }
+            };
+
+            if live {
+                match last_live {
+                    Some(last) => self.occupied_mut(last).next = Some(index),
+                    None => self.head = Some(index),
+                }
+
+                last_live = Some(index);
+            } else {
+                self.free_slot(index);
+            }
+
+            next = after;
+        }
+
+        if let Some(last) = last_live {
+            self.occupied_mut(last).next = None;
+        }
+
+        self.tail = last_live;
+        self.tombstones = 0;
+
+        self.trim_if_auto();
+    }
+
+    /// Returns mutable references to the items at each of these indexes, in
+    /// the same order as the indexes.
+    ///
+    /// Returns `None` if any of the indexes are stale, or if the same item is
+    /// asked for more than once, since we can't hand out two mutable
+    /// references to it.
+    ///
+    /// # Examples
+    ///
+    /// Changing several items at once:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let one = list.push_back(1);
+    /// let two = list.push_back(2);
+    /// let three = list.push_back(3);
+    ///
+    /// if let Some(items) = list.get_disjoint_mut(&[three, one]) {
+    ///     for item in items {
+    ///         *item *= 10;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&10, &2, &30]);
+    ///
+    /// // asking for the same item twice doesn't work
+    /// assert!(list.get_disjoint_mut(&[two, two]).is_none());
+    /// ```
+    pub fn get_disjoint_mut(&mut self, indexes: &[Index<T>]) -> Option<Vec<&mut T>> {
+        if indexes.iter().any(|&index| self.get(index).is_none()) {
+            return None;
+        }
+
+        // sort the slots so we can split them off of the vector one at a time,
+        // remembering where each one was asked for
+        let mut slots: Vec<(usize, usize)> = indexes
+            .iter()
+            .enumerate()
+            .map(|(position, index)| (index.index, position))
+            .collect();
+        slots.sort();
+
+        if slots.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return None;
+        }
+
+        let mut items: Vec<Option<&mut T>> = indexes.iter().map(|_| None).collect();
+        let mut rest = &mut self.contents[..];
+        let mut offset = 0;
+
+        for (slot, position) in slots {
+            let (entry, tail) = std::mem::take(&mut rest)[slot - offset..]
+                .split_first_mut()
+                .unwrap();
+
+            match entry {
+                Entry::Occupied(e) => items[position] = Some(&mut e.item),
+                _ => panic!("Corrupted list"),
+            }
+
+            rest = tail;
+            offset = slot + 1;
+        }
+
+        Some(items.into_iter().map(Option::unwrap).collect())
+    }
+
+    /// Inserts an element immediately before the provided index, or reports
+    /// why it couldn't.
+    ///
+    /// This is like `insert_before`, but if the insertion fails, you get back
+    /// an [`InsertError`] saying why, along with the item you tried to insert.
+    ///
+    /// [`InsertError`]: enum.InsertError.html
+    ///
+    /// # Examples
+    ///
+    /// Trying to insert before a removed item:
+    ///
+    /// ```
+    /// use indexlist::{IndexList, InsertError};
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.remove(five);
+    ///
+    /// // this reuses five's slot
+    /// list.push_back(10);
+    ///
+    /// assert_eq!(
+    ///     list.try_insert_before(five, 0),
+    ///     Err((InsertError::StaleGeneration, 0))
+    /// );
+    /// ```
+    pub fn try_insert_before(
+        &mut self,
+        index: Index<T>,
+        item: T,
+    ) -> Result<Index<T>, (InsertError, T)> {
+        match self.check_insert(index) {
+            // we just checked the index, so this will succeed
+            Ok(()) => Ok(self.insert_before(index, item).unwrap()),
+            Err(e) => Err((e, item)),
+        }
+    }
+
+    /// Inserts an element immediately after the provided index, or reports
+    /// why it couldn't.
+    ///
+    /// This is like `insert_after`, but if the insertion fails, you get back
+    /// an [`InsertError`] saying why, along with the item you tried to insert.
+    ///
+    /// [`InsertError`]: enum.InsertError.html
+    ///
+    /// # Examples
+    ///
+    /// Trying to insert after a removed item:
+    ///
+    /// ```
+    /// use indexlist::{IndexList, InsertError};
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    /// list.remove(five);
+    ///
+    /// assert_eq!(
+    ///     list.try_insert_after(five, 0),
+    ///     Err((InsertError::SlotFree, 0))
+    /// );
+    /// ```
+    pub fn try_insert_after(
+        &mut self,
+        index: Index<T>,
+        item: T,
+    ) -> Result<Index<T>, (InsertError, T)> {
+        match self.check_insert(index) {
+            // we just checked the index, so this will succeed
+            Ok(()) => Ok(self.insert_after(index, item).unwrap()),
+            Err(e) => Err((e, item)),
+        }
+    }
+
+    // works out whether we can insert next to this index, and if not, why
+    fn check_insert(&self, index: Index<T>) -> Result<(), InsertError> {
+        match self.get_checked(index) {
+            Ok(_) => Ok(()),
+            Err(IndexError::OutOfBounds) => Err(InsertError::OutOfBounds),
+            Err(IndexError::Stale) => Err(InsertError::StaleGeneration),
+            Err(IndexError::Freed) => Err(InsertError::SlotFree),
+        }
+    }
+
+    /// Returns the index of the first item in the list, along with a mutable
+    /// reference to it.
+    ///
+    /// Will return `None` if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// Updating the head and keeping track of where it is:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// let (index, head) = list.head_entry_mut().unwrap();
+    /// *head += 1;
+    ///
+    /// assert_eq!(list.get(index), Some(&6));
+    /// ```
+    pub fn head_entry_mut(&mut self) -> Option<(Index<T>, &mut T)> {
+        let index = self.head_index()?;
+
+        Some((index, &mut self.occupied_mut(index.index).item))
+    }
+
+    /// Returns the index of the last item in the list, along with a mutable
+    /// reference to it.
+    ///
+    /// Will return `None` if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// Updating the tail and keeping track of where it is:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// list.push_back(10);
+    ///
+    /// let (index, tail) = list.tail_entry_mut().unwrap();
+    /// *tail += 1;
+    ///
+    /// assert_eq!(list.get(index), Some(&11));
+    /// ```
+    pub fn tail_entry_mut(&mut self) -> Option<(Index<T>, &mut T)> {
+        let index = self.tail_index()?;
+
+        Some((index, &mut self.occupied_mut(index.index).item))
+    }
+
+    /// Combines this list with another one, element by element.
+    ///
+    /// Both lists are consumed. Items are paired up in list order, and `f` is
+    /// called on each pair to produce the items of the new list. If one list
+    /// is longer than the other, its extra items are dropped.
+    ///
+    /// # Examples
+    ///
+    /// Adding two lists together:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// list.push_back(3);
+    ///
+    /// let mut other = IndexList::new();
+    ///
+    /// other.push_back(10);
+    /// other.push_back(20);
+    ///
+    /// let sums = list.zip_with(other, |a, b| a + b);
+    ///
+    /// assert_eq!(sums.iter().cloned().collect::<Vec<_>>(), [11, 22]);
+    /// ```
+    pub fn zip_with<U, V, F>(self, other: IndexList<U>, mut f: F) -> IndexList<V>
+    where
+        U: PartialEq + std::fmt::Debug,
+        V: PartialEq + std::fmt::Debug,
+        F: FnMut(T, U) -> V,
+    {
+        let mut list = IndexList::with_capacity(std::cmp::min(self.len(), other.len()));
+
+        for (a, b) in self.into_iter().zip(other) {
+            list.push_back(f(a, b));
+        }
+
+        list
+    }
+
+    /// Folds over the list from head to tail, passing each item's `Index`
+    /// along with a reference to it.
+    ///
+    /// # Examples
+    ///
+    /// Finding the index of the largest item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    /// let ten = list.push_back(10);
+    /// list.push_back(2);
+    ///
+    /// let largest = list.fold_indexed(None, |best, index, &item| match best {
+    ///     Some((_, n)) if n >= item => best,
+    ///     _ => Some((index, item)),
+    /// });
+    ///
+    /// assert_eq!(largest, Some((ten, 10)));
+    /// ```
+    pub fn fold_indexed<B, F>(&self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Index<T>, &T) -> B,
+    {
+        self.iter_with_indexes()
+            .fold(init, |acc, (index, item)| f(acc, index, item))
+    }
+
+    /// Splits the list in two at this index, and returns the new list along
+    /// with the indexes of its head and tail.
+    ///
+    /// After this call, `self` contains everything before the index, and the
+    /// returned list contains the item at the index and everything after it.
+    /// The moved items get new indexes in the returned list, so any old
+    /// indexes to them are no longer valid.
+    ///
+    /// Returns `None` and leaves the list alone if the index isn't valid.
+    ///
+    /// # Examples
+    ///
+    /// Splitting a list in half:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// let three = list.push_back(3);
+    /// list.push_back(4);
+    ///
+    /// let (back, head, tail) = list.split_off_tracked(three).unwrap();
+    ///
+    /// assert_eq!(back.get(head), Some(&3));
+    /// assert_eq!(back.get(tail), Some(&4));
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    pub fn split_off_tracked(
+        &mut self,
+        index: Index<T>,
+    ) -> Option<(IndexList<T>, Index<T>, Index<T>)> {
+        self.get(index)?;
+
+        let mut other = IndexList::new();
+        let mut next = Some(index);
+
+        while let Some(index) = next {
+            next = self.next_index(index);
+
+            // every index we walk to is valid
+            other.push_back(self.remove(index).unwrap());
+        }
+
+        // we moved at least the item at `index`, so there's a head and a tail
+        let head = other.head_index().unwrap();
+        let tail = other.tail_index().unwrap();
+
+        Some((other, head, tail))
+    }
+
+    /// Checks an index against this list, and returns an index to the same
+    /// slot with this list's generation for it.
+    ///
+    /// Indexes belong to the list that made them, but nothing stops you from
+    /// using one with a different list, say, after a `std::mem::swap`. This
+    /// lets you re-check an index explicitly: if its slot holds an item in
+    /// this list, you get back a fresh index for that item, and if it doesn't,
+    /// you get `None`.
+    ///
+    /// Note that this only looks at the slot, not the generation, so the item
+    /// you get an index to may not be the one the old index pointed at.
+    ///
+    /// # Examples
+    ///
+    /// Re-checking indexes after swapping two lists:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut a = IndexList::new();
+    /// let one = a.push_back(1);
+    /// let two = a.push_back(2);
+    ///
+    /// let mut b = IndexList::new();
+    /// b.push_back(10);
+    ///
+    /// std::mem::swap(&mut a, &mut b);
+    ///
+    /// // `a` only has one item now, so `two`'s slot is empty
+    /// assert_eq!(a.rebind(two), None);
+    ///
+    /// // `one`'s slot holds 10 in `a`
+    /// let ten = a.rebind(one).unwrap();
+    /// assert_eq!(a.get(ten), Some(&10));
+    ///
+    /// // and `b` still has everything `one` and `two` pointed at
+    /// assert_eq!(b.rebind(two), Some(two));
+    /// ```
+    pub fn rebind(&self, index: Index<T>) -> Option<Index<T>> {
+        match self.contents.get(index.index)? {
+            Entry::Free { .. } | Entry::Tombstone { .. } => None,
+            Entry::Occupied(e) => Some(Index::new(index.index, e.generation)),
+        }
+    }
+
+    /// Moves all of the items in `other` to the back of this list, without
+    /// copying them one at a time, and returns their new indexes.
+    ///
+    /// `other`'s storage is moved onto the end of this list's storage, so
+    /// each of its items moves to a new slot, and is given a generation newer
+    /// than either list's. That way, no stale index into either list can
+    /// resolve to a moved item. The returned indexes are in list order, so
+    /// you can use them to refresh any indexes you held into `other`. Any
+    /// free slots in `other` are kept, and can be reused by this list.
+    ///
+    /// # Examples
+    ///
+    /// Appending a list and finding its items again:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// list.push_back(2);
+    ///
+    /// let mut other = IndexList::new();
+    ///
+    /// other.push_back(3);
+    /// other.push_back(4);
+    ///
+    /// let moved = list.append_preserving(other);
+    ///
+    /// assert_eq!(list.get(moved[0]), Some(&3));
+    /// assert_eq!(list.get(moved[1]), Some(&4));
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// ```
+    pub fn append_preserving(&mut self, mut other: IndexList<T>) -> Vec<Index<T>> {
+        self.sweep();
+        other.sweep();
+
+        // moved items land in slots that stale indexes from either list might
+        // still point at, so they get a generation newer than all of those
+        let generation = std::cmp::max(self.generation, other.generation) + 1;
+
+        // every slot in `other` moves up by this much
+        let offset = self.contents.len();
+        let shift = |slot: Option<usize>| slot.map(|slot| slot + offset);
+
+        for entry in &mut other.contents {
+            match entry {
+                Entry::Occupied(e) => {
+                    e.next = shift(e.next);
+                    e.prev = shift(e.prev);
+                    e.generation = generation;
+                }
+                // the end of other's free list continues on to ours
+                Entry::Free { next_free } => {
+                    *next_free = match *next_free {
+                        Some(slot) => Some(slot + offset),
+                        None => self.next_free,
+                    };
+                }
+                Entry::Tombstone { .. } => panic!("Corrupted list"),
+            }
+        }
+
+        if other.next_free.is_some() {
+            self.next_free = shift(other.next_free);
+        }
+
+        // if we had free slots, ours are still at the end of the merged list
+        if self.free_tail.is_none() {
+            self.free_tail = shift(other.free_tail);
+        }
+
+        let other_head = shift(other.head);
+        let other_tail = shift(other.tail);
+
+        self.contents.append(&mut other.contents);
+
+        match (self.tail, other_head) {
+            (Some(tail), Some(head)) => {
+                self.occupied_mut(tail).next = Some(head);
+                self.occupied_mut(head).prev = Some(tail);
+                self.tail = other_tail;
+            }
+            (None, Some(_)) => {
+                self.head = other_head;
+                self.tail = other_tail;
+            }
+            // nothing to link up
+            (_, None) => {}
+        }
+
+        self.len += other.len;
+        self.generation = generation;
+
+        let mut moved = Vec::with_capacity(other.len);
+        let mut next = other_head;
+
+        while let Some(slot) = next {
+            let e = self.occupied(slot);
+
+            moved.push(Index::new(slot, e.generation));
+            next = e.next;
+        }
+
+        moved
+    }
+
+    /// Returns the head of the list if it matches a predicate, and otherwise
+    /// pushes this item onto the front and returns that.
+    ///
+    /// # Examples
+    ///
+    /// Only pushing an item if it isn't already at the front:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(5);
+    ///
+    /// assert_eq!(list.front_or_push(|&n| n == 10, 10), &10);
+    /// assert_eq!(list.front_or_push(|&n| n == 10, 10), &10);
+    ///
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    pub fn front_or_push<P>(&mut self, pred: P, item: T) -> &T
+    where
+        P: FnMut(&T) -> bool,
+    {
+        if !self.head().is_some_and(pred) {
+            self.push_front(item);
+        }
+
+        // either it was already there, or we just pushed it
+        self.head().unwrap()
+    }
+
+    /// Adds this item to the tail of the list, and returns its index along
+    /// with a mutable reference to it.
+    ///
+    /// # Examples
+    ///
+    /// Pushing an item and then changing it:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let (five, item) = list.push_back_mut(5);
+    /// *item += 1;
+    ///
+    /// assert_eq!(list.get(five), Some(&6));
+    /// ```
+    pub fn push_back_mut(&mut self, item: T) -> (Index<T>, &mut T) {
+        let index = self.push_back(item);
+
+        (index, &mut self.occupied_mut(index.index).item)
+    }
+
+    /// Adds this item to the head of the list, and returns its index along
+    /// with a mutable reference to it.
+    ///
+    /// # Examples
+    ///
+    /// Pushing an item and then changing it:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(10);
+    ///
+    /// let (five, item) = list.push_front_mut(5);
+    /// *item += 1;
+    ///
+    /// assert_eq!(list.head_index(), Some(five));
+    /// assert_eq!(list.head(), Some(&6));
+    /// ```
+    pub fn push_front_mut(&mut self, item: T) -> (Index<T>, &mut T) {
+        let index = self.push_front(item);
+
+        (index, &mut self.occupied_mut(index.index).item)
+    }
+
+    /// Inserts an element immediately before the provided index, and returns
+    /// the new element's index along with a mutable reference to it.
+    ///
+    /// Returns `None` if the provided index isn't valid.
+    ///
+    /// # Examples
+    ///
+    /// Inserting an item and then changing it:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let (four, item) = list.insert_before_mut(five, 3).unwrap();
+    /// *item += 1;
+    ///
+    /// assert_eq!(list.get(four), Some(&4));
+    /// assert_eq!(list.head_index(), Some(four));
+    /// ```
+    pub fn insert_before_mut(&mut self, index: Index<T>, item: T) -> Option<(Index<T>, &mut T)> {
+        let index = self.insert_before(index, item)?;
+
+        Some((index, &mut self.occupied_mut(index.index).item))
+    }
+
+    /// Inserts an element immediately after the provided index, and returns
+    /// the new element's index along with a mutable reference to it.
+    ///
+    /// Returns `None` if the provided index isn't valid.
+    ///
+    /// # Examples
+    ///
+    /// Inserting an item and then changing it:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let (six, item) = list.insert_after_mut(five, 5).unwrap();
+    /// *item += 1;
+    ///
+    /// assert_eq!(list.get(six), Some(&6));
+    /// assert_eq!(list.tail_index(), Some(six));
+    /// ```
+    pub fn insert_after_mut(&mut self, index: Index<T>, item: T) -> Option<(Index<T>, &mut T)> {
+        let index = self.insert_after(index, item)?;
+
+        Some((index, &mut self.occupied_mut(index.index).item))
+    }
+
+    /// Returns references to the items from one index to another, including
+    /// both ends, in list order.
+    ///
+    /// Returns `None` if either index isn't valid, or if `to` doesn't come
+    /// after `from` in the list.
+    ///
+    /// # Examples
+    ///
+    /// Getting the middle of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// let two = list.push_back(2);
+    /// list.push_back(3);
+    /// let four = list.push_back(4);
+    /// list.push_back(5);
+    ///
+    /// assert_eq!(list.collect_between(two, four), Some(vec![&2, &3, &4]));
+    /// assert_eq!(list.collect_between(four, two), None);
+    /// ```
+    pub fn collect_between(&self, from: Index<T>, to: Index<T>) -> Option<Vec<&T>> {
+        self.get(to)?;
+
+        let mut items = vec![self.get(from)?];
+        let mut current = from;
+
+        while current != to {
+            // if we run off the end, `to` wasn't after `from`
+            current = self.next_index(current)?;
+            items.push(&self[current]);
+        }
+
+        Some(items)
+    }
+
+    /// Joins the items in the list into one `String`, with a separator
+    /// between each one.
+    ///
+    /// # Examples
+    ///
+    /// Joining some words:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back("a");
+    /// list.push_back("b");
+    /// list.push_back("c");
+    ///
+    /// assert_eq!(list.join("-"), "a-b-c");
+    /// ```
+    pub fn join(&self, sep: &str) -> String
+    where
+        T: AsRef<str>,
+    {
+        let mut joined = String::new();
+
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                joined.push_str(sep);
+            }
+
+            joined.push_str(item.as_ref());
+        }
+
+        joined
+    }
+
+    /// Adds up all of the items in the list.
+    ///
+    /// This is the same as `list.iter().sum()`.
+    ///
+    /// # Examples
+    ///
+    /// Summing some numbers:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// list.push_back(2);
+    /// list.push_back(3);
+    ///
+    /// assert_eq!(list.sum::<i32>(), 6);
+    /// ```
+    pub fn sum<'a, S>(&'a self) -> S
+    where
+        S: std::iter::Sum<&'a T>,
+    {
+        self.iter().sum()
+    }
+
+    /// Multiplies all of the items in the list together.
+    ///
+    /// This is the same as `list.iter().product()`.
+    ///
+    /// # Examples
+    ///
+    /// Multiplying some numbers:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(2);
+    /// list.push_back(3);
+    /// list.push_back(4);
+    ///
+    /// assert_eq!(list.product::<i32>(), 24);
+    /// ```
+    pub fn product<'a, P>(&'a self) -> P
+    where
+        P: std::iter::Product<&'a T>,
+    {
+        self.iter().product()
+    }
+
+    /// Returns the index of the smallest item in the list.
+    ///
+    /// If there's more than one smallest item, this returns the index of the
+    /// one closest to the head. Returns `None` if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// Finding and removing the smallest item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(3);
+    /// list.push_back(1);
+    /// list.push_back(4);
+    ///
+    /// let min = list.min_index().unwrap();
+    ///
+    /// assert_eq!(list.remove(min), Some(1));
+    /// ```
+    pub fn min_index(&self) -> Option<Index<T>>
+    where
+        T: Ord,
+    {
+        self.extreme_index(std::cmp::Ordering::Less)
+    }
+
+    /// Returns the index of the largest item in the list.
+    ///
+    /// If there's more than one largest item, this returns the index of the
+    /// one closest to the head. Returns `None` if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// Finding and removing the largest item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(3);
+    /// list.push_back(5);
+    /// list.push_back(4);
+    ///
+    /// let max = list.max_index().unwrap();
+    ///
+    /// assert_eq!(list.remove(max), Some(5));
+    /// ```
+    pub fn max_index(&self) -> Option<Index<T>>
+    where
+        T: Ord,
+    {
+        self.extreme_index(std::cmp::Ordering::Greater)
+    }
+
+    // scans for the first item that nothing else beats, where beating means
+    // comparing as `wanted`: `Less` finds the minimum, `Greater` the maximum
+    fn extreme_index(&self, wanted: std::cmp::Ordering) -> Option<Index<T>>
+    where
+        T: Ord,
+    {
+        let mut best: Option<(Index<T>, &T)> = None;
+
+        for (index, item) in self.iter_with_indexes() {
+            match best {
+                // only replace on a strict win, so ties go to the earlier item
+                Some((_, current)) if item.cmp(current) != wanted => {}
+                _ => best = Some((index, item)),
+            }
+        }
+
+        best.map(|(index, _)| index)
+    }
+
+    /// Consumes the list, and returns an iterator over its items from
+    /// smallest to largest.
+    ///
+    /// This collects the items into a `Vec` and sorts it up front, so it
+    /// costs O(n log n) time and O(n) extra space before the first item comes
+    /// out.
+    ///
+    /// # Examples
+    ///
+    /// Draining a list in order:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(3);
+    /// list.push_back(1);
+    /// list.push_back(2);
+    ///
+    /// let sorted: Vec<_> = list.drain_sorted().collect();
+    ///
+    /// assert_eq!(sorted, vec![1, 2, 3]);
+    /// ```
+    pub fn drain_sorted(self) -> impl Iterator<Item = T>
+    where
+        T: Ord,
+    {
+        self.into_sorted_vec().into_iter()
+    }
+
+    /// Adds this item to the tail of the list, and reports whether it went
+    /// into a free slot.
+    ///
+    /// The `bool` is `true` if the item reused a slot freed by an earlier
+    /// removal, and `false` if the underlying vector had to grow.
+    ///
+    /// # Examples
+    ///
+    /// Seeing a slot get reused:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let (five, reused) = list.push_back_tracked(5);
+    /// assert!(!reused);
+    ///
+    /// list.remove(five);
+    ///
+    /// let (_, reused) = list.push_back_tracked(10);
+    /// assert!(reused);
+    /// ```
+    pub fn push_back_tracked(&mut self, item: T) -> (Index<T>, bool) {
+        // sweeping can free up slots, so do it before we look
+        self.sweep();
+
+        let reused = self.next_free.is_some();
+
+        (self.push_back(item), reused)
+    }
+
+    /// Consumes the list, and splits it into pieces wherever an item matches
+    /// a predicate.
+    ///
+    /// The matching items themselves are dropped. Like `slice::split`, a
+    /// match at either end, or two matches next to each other, produce empty
+    /// pieces.
+    ///
+    /// # Examples
+    ///
+    /// Splitting on zeroes:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 0, 3, 0, 4]);
+    ///
+    /// let pieces: Vec<Vec<_>> = list
+    ///     .split_by(|&n| n == 0)
+    ///     .into_iter()
+    ///     .map(|piece| piece.into_iter().collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(pieces, vec![vec![1, 2], vec![3], vec![4]]);
+    /// ```
+    pub fn split_by<F>(self, mut pred: F) -> Vec<IndexList<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut pieces = vec![IndexList::new()];
+
+        for item in self {
+            if pred(&item) {
+                pieces.push(IndexList::new());
+            } else {
+                // there's always at least one piece
+                pieces.last_mut().unwrap().push_back(item);
+            }
+        }
+
+        pieces
+    }
+
+    /// Takes all of the items out of this list, and returns them as a new
+    /// list, leaving this one empty.
+    ///
+    /// This is like `std::mem::take`, except that any indexes you held into
+    /// this list won't accidentally line up with items pushed into it later.
+    /// They stay valid in the returned list, though.
+    ///
+    /// # Examples
+    ///
+    /// Taking the items out of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let taken = list.take();
+    ///
+    /// assert!(list.is_empty());
+    /// assert_eq!(taken.get(five), Some(&5));
+    ///
+    /// list.push_back(10);
+    ///
+    /// assert_eq!(list.get(five), None);
+    /// ```
+    pub fn take(&mut self) -> IndexList<T> {
+        // emptying the list counts as a change of generation, so that its old
+        // indexes won't line up with anything pushed into it later
+        let generation = self.generation + 1;
+        let auto_trim = self.auto_trim;
+        let free_strategy = self.free_strategy;
+
+        let taken = std::mem::replace(self, IndexList::new());
+
+        self.generation = generation;
+        self.auto_trim = auto_trim;
+        self.free_strategy = free_strategy;
+
+        taken
+    }
+
+    /// Returns an iterator of mutable references to the items at positions
+    /// `start..end` in the list.
+    ///
+    /// Positions count from the head, starting at zero. If `end` is past the
+    /// end of the list, the iterator stops at the tail, and if `start` isn't
+    /// before `end`, it yields nothing.
+    ///
+    /// # Examples
+    ///
+    /// Doubling a few items in the middle of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+    ///
+    /// for item in list.range_mut(1, 3) {
+    ///     *item *= 2;
+    /// }
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 4, 6, 4, 5]);
+    /// ```
+    pub fn range_mut(&mut self, start: usize, end: usize) -> impl Iterator<Item = &mut T> {
+        // walk the links just far enough to find the items we want
+        let indexes: Vec<Index<T>> = self
+            .iter_with_indexes()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .map(|(index, _)| index)
+            .collect();
+
+        // these all came from walking the list, so they're valid and distinct
+        self.get_disjoint_mut(&indexes).unwrap().into_iter()
+    }
+
+    /// Adds all of these items to the tail of the list, in order, and returns
+    /// their indexes.
+    ///
+    /// Space for all of the items is reserved up front, so the underlying
+    /// vector grows at most once.
+    ///
+    /// # Examples
+    ///
+    /// Pushing several items at once:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let indexes = list.batch_push_back(vec![5, 10, 15]);
+    ///
+    /// assert_eq!(list.get(indexes[1]), Some(&10));
+    /// assert_eq!(list.len(), 3);
+    /// ```
+    pub fn batch_push_back(&mut self, items: Vec<T>) -> Vec<Index<T>> {
+        self.reserve(items.len());
+
+        items.into_iter().map(|item| self.push_back(item)).collect()
+    }
+
+    /// Returns `true` if every one of these items is in the list.
+    ///
+    /// This checks each item separately, so it takes O(n * m) time; it's best
+    /// for a handful of items.
+    ///
+    /// # Examples
+    ///
+    /// Checking for several items:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert!(list.contains_all(&[1, 3]));
+    /// assert!(!list.contains_all(&[1, 4]));
+    /// ```
+    pub fn contains_all(&self, items: &[T]) -> bool {
+        items.iter().all(|item| self.contains(item))
+    }
+
+    /// Returns `true` if at least one of these items is in the list.
+    ///
+    /// This checks each item separately, so it takes O(n * m) time; it's best
+    /// for a handful of items.
+    ///
+    /// # Examples
+    ///
+    /// Checking for any of several items:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert!(list.contains_any(&[9, 3]));
+    /// assert!(!list.contains_any(&[9, 8]));
+    /// ```
+    pub fn contains_any(&self, items: &[T]) -> bool {
+        self.iter().any(|e| items.contains(e))
+    }
+
+    /// Removes every item that's equal to an item closer to the head, so that
+    /// only the first copy of each value is left.
+    ///
+    /// # Examples
+    ///
+    /// Removing duplicates:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 1, 3, 2]);
+    ///
+    /// list.dedup_all();
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    pub fn dedup_all(&mut self)
+    where
+        T: Eq + std::hash::Hash,
+    {
+        let duplicates: Vec<Index<T>> = {
+            let mut seen = std::collections::HashSet::new();
+
+            self.iter_with_indexes()
+                .filter(|&(_, item)| !seen.insert(item))
+                .map(|(index, _)| index)
+                .collect()
+        };
+
+        for index in duplicates {
+            self.remove(index);
+        }
+    }
+
+    /// Swaps the items at the head and the tail of the list.
+    ///
+    /// Only the items move; the nodes stay where they are. That means the
+    /// head's index now refers to the old tail item, and the other way
+    /// around.
+    ///
+    /// Returns `false` and does nothing if the list has fewer than two items.
+    ///
+    /// # Examples
+    ///
+    /// Swapping the ends of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert!(list.swap_ends());
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [3, 2, 1]);
+    /// ```
+    pub fn swap_ends(&mut self) -> bool {
+        match self.head_tail_mut() {
+            Some((head, tail)) => {
+                std::mem::swap(head, tail);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Keeps only the first `n` items that match a predicate, and removes any
+    /// matching items after that.
+    ///
+    /// Items that don't match are always kept. The predicate is called once
+    /// for each item, from head to tail.
+    ///
+    /// # Examples
+    ///
+    /// Keeping only the first two odd numbers:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+    ///
+    /// list.retain_first_n(2, |n| n % 2 == 1);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    /// ```
+    pub fn retain_first_n<F>(&mut self, n: usize, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut matched = 0;
+
+        let extra: Vec<Index<T>> = self
+            .iter_with_indexes()
+            .filter(|&(_, item)| {
+                if !f(item) {
+                    return false;
+                }
+
+                matched += 1;
+                matched > n
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        for index in extra {
+            self.remove(index);
+        }
+    }
+
+    /// Returns the indexes of every item in the list, in list order.
+    ///
+    /// Since this is a copy, the list isn't borrowed afterwards, so you can
+    /// go through the indexes and change the list as you go.
+    ///
+    /// # Examples
+    ///
+    /// Removing every other item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4]);
+    ///
+    /// for index in list.index_snapshot().into_iter().step_by(2) {
+    ///     list.remove(index);
+    /// }
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 4]);
+    /// ```
+    pub fn index_snapshot(&self) -> Vec<Index<T>> {
+        self.iter_with_indexes().map(|(index, _)| index).collect()
+    }
+
+    /// Moves an item so that it sits immediately before another one.
+    ///
+    /// The moved item keeps its slot, so its `Index` stays valid. Returns
+    /// `false` and does nothing if either index isn't valid, or if they're the
+    /// same index.
+    ///
+    /// # Examples
+    ///
+    /// Moving the tail to the front:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let one = list.push_back(1);
+    /// list.push_back(2);
+    /// let three = list.push_back(3);
+    ///
+    /// assert!(list.move_before(three, one));
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [3, 1, 2]);
+    /// assert_eq!(list.head_index(), Some(three));
+    /// ```
+    pub fn move_before(&mut self, node: Index<T>, target: Index<T>) -> bool {
+        if !self.can_move(node, target) {
+            return false;
+        }
+
+        self.detach(node.index);
+        self.attach_before(node.index, target.index);
+
+        true
+    }
+
+    /// Moves an item so that it sits immediately after another one.
+    ///
+    /// The moved item keeps its slot, so its `Index` stays valid. Returns
+    /// `false` and does nothing if either index isn't valid, or if they're the
+    /// same index.
+    ///
+    /// # Examples
+    ///
+    /// Moving the head to the back:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let one = list.push_back(1);
+    /// list.push_back(2);
+    /// let three = list.push_back(3);
+    ///
+    /// assert!(list.move_after(one, three));
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 3, 1]);
+    /// assert_eq!(list.tail_index(), Some(one));
+    /// ```
+    pub fn move_after(&mut self, node: Index<T>, target: Index<T>) -> bool {
+        if !self.can_move(node, target) {
+            return false;
+        }
+
+        self.detach(node.index);
+        self.attach_after(node.index, target.index);
+
+        true
+    }
+
+    // checks that `node` can be moved next to `target`
+    fn can_move(&mut self, node: Index<T>, target: Index<T>) -> bool {
+        // relinking assumes every neighbour is occupied
+        self.sweep();
+
+        node != target && self.get(node).is_some() && self.get(target).is_some()
+    }
+
+    /// Consumes the list, and splits it into runs of consecutive items that
+    /// have the same key.
+    ///
+    /// # Examples
+    ///
+    /// Grouping equal numbers:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 1, 2, 3, 3]);
+    ///
+    /// let groups: Vec<Vec<_>> = list
+    ///     .group_by(|&n| n)
+    ///     .into_iter()
+    ///     .map(|group| group.into_iter().collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(groups, vec![vec![1, 1], vec![2], vec![3, 3]]);
+    /// ```
+    pub fn group_by<K, F>(self, mut key: F) -> Vec<IndexList<T>>
+    where
+        K: PartialEq,
+        F: FnMut(&T) -> K,
+    {
+        let mut groups: Vec<IndexList<T>> = Vec::new();
+        let mut current_key = None;
+
+        for item in self {
+            let k = key(&item);
+
+            if current_key.as_ref() != Some(&k) {
+                groups.push(IndexList::new());
+                current_key = Some(k);
+            }
+
+            // we pushed a group the first time through
+            groups.last_mut().unwrap().push_back(item);
+        }
+
+        groups
+    }
+
+    /// Replaces every item that's equal to `old` with a clone of `new`, and
+    /// returns how many were replaced.
+    ///
+    /// The items are replaced in place, so their indexes stay valid.
+    ///
+    /// # Examples
+    ///
+    /// Replacing every one with a nine:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 1, 3]);
+    ///
+    /// assert_eq!(list.replace_all(&1, 9), 2);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [9, 2, 9, 3]);
+    /// ```
+    pub fn replace_all(&mut self, old: &T, new: T) -> usize
+    where
+        T: Clone,
+    {
+        let mut replaced = 0;
+
+        for (_, item) in self.iter_mut_indexed() {
+            if item == old {
+                *item = new.clone();
+                replaced += 1;
+            }
+        }
+
+        replaced
+    }
+
+    /// Removes the first `n` items from the list.
+    ///
+    /// If the list has `n` items or fewer, it ends up empty.
+    ///
+    /// # Examples
+    ///
+    /// Dropping the first two items:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4]);
+    ///
+    /// list.truncate_front(2);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [3, 4]);
+    /// ```
+    pub fn truncate_front(&mut self, n: usize) {
+        for _ in 0..n {
+            if self.pop_front().is_none() {
+                break;
+            }
+        }
+    }
+
+    /// Returns the length of each run of consecutive equal items, along with
+    /// a reference to the first item in the run.
+    ///
+    /// # Examples
+    ///
+    /// Run-length encoding a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 1, 2, 3, 3, 3]);
+    ///
+    /// assert_eq!(list.run_lengths(), vec![(2, &1), (1, &2), (3, &3)]);
+    /// ```
+    pub fn run_lengths(&self) -> Vec<(usize, &T)> {
+        let mut runs: Vec<(usize, &T)> = Vec::new();
+
+        for item in self.iter() {
+            match runs.last_mut() {
+                Some((count, first)) if *first == item => *count += 1,
+                _ => runs.push((1, item)),
+            }
+        }
+
+        runs
+    }
+
+    /// Returns a reference to the first item in the list, without removing
+    /// it.
+    ///
+    /// This is the same as `head`, named to match `pop_front`.
+    ///
+    /// # Examples
+    ///
+    /// Looking at the front of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(list.peek_front(), Some(&1));
+    /// ```
+    pub fn peek_front(&self) -> Option<&T> {
+        self.head()
+    }
+
+    /// Returns a reference to the last item in the list, without removing it.
+    ///
+    /// This is named to match `pop_back`.
+    ///
+    /// # Examples
+    ///
+    /// Looking at the back of a list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(list.peek_back(), Some(&3));
+    /// ```
+    pub fn peek_back(&self) -> Option<&T> {
+        self.peek_nth_back(0)
+    }
+
+    /// Returns a reference to the `n`th item from the front of the list,
+    /// counting from zero.
+    ///
+    /// Returns `None` if the list has `n` items or fewer.
+    ///
+    /// # Examples
+    ///
+    /// Looking at the second item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(list.peek_nth_front(1), Some(&2));
+    /// assert_eq!(list.peek_nth_front(3), None);
+    /// ```
+    pub fn peek_nth_front(&self, n: usize) -> Option<&T> {
+        self.iter().nth(n)
+    }
+
+    /// Returns a reference to the `n`th item from the back of the list,
+    /// counting from zero.
+    ///
+    /// This walks backwards from the tail, so the time it takes grows with `n`,
+    /// and doesn't depend on how long the list is.
+    ///
+    /// Returns `None` if the list has `n` items or fewer.
+    ///
+    /// # Examples
+    ///
+    /// Looking at the second-to-last item:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(list.peek_nth_back(1), Some(&2));
+    /// assert_eq!(list.peek_nth_back(3), None);
+    /// ```
+    pub fn peek_nth_back(&self, n: usize) -> Option<&T> {
+        let mut index = self.tail_index()?;
+
+        for _ in 0..n {
+            index = self.prev_index(index)?;
+        }
+
+        self.get(index)
+    }
+
+    /// Removes every item whose matching position in `mask` is `false`.
+    ///
+    /// The two lists are walked side by side. If `mask` is shorter than this
+    /// list, the items past its end are kept.
+    ///
+    /// # Examples
+    ///
+    /// Keeping the first and last items:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[10, 20, 30]);
+    /// let mask = IndexList::from_slice(&[true, false, true]);
+    ///
+    /// list.retain_mask(&mask);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10, 30]);
+    /// ```
+    pub fn retain_mask(&mut self, mask: &IndexList<bool>) {
+        let dropped: Vec<Index<T>> = self
+            .iter_with_indexes()
+            .zip(mask.iter())
+            .filter(|&(_, &keep)| !keep)
+            .map(|((index, _), _)| index)
+            .collect();
+
+        for index in dropped {
+            self.remove(index);
+        }
+    }
+
+    /// Consumes the list, and returns an iterator over its items along with
+    /// the index each one had, in list order.
+    ///
+    /// # Examples
+    ///
+    /// Consuming a list while keeping track of indexes:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// let ten = list.push_back(10);
+    ///
+    /// let pairs: Vec<_> = list.into_iter_indexed().collect();
+    ///
+    /// assert_eq!(pairs, vec![(five, 5), (ten, 10)]);
+    /// ```
+    pub fn into_iter_indexed(self) -> impl Iterator<Item = (Index<T>, T)> {
+        let mut iter = self.into_iter();
+
+        std::iter::from_fn(move || {
+            iter.next_entry()
+                .map(|(slot, e)| (Index::new(slot, e.generation), e.item))
+        })
+    }
+
+    /// Returns how many steps it takes to walk forward from one index to
+    /// another.
+    ///
+    /// Returns `None` if either index isn't valid, or if `to` doesn't come
+    /// after `from` in the list.
+    ///
+    /// # Examples
+    ///
+    /// Measuring the distance between two items:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// let two = list.push_back(2);
+    /// list.push_back(3);
+    /// let four = list.push_back(4);
+    ///
+    /// assert_eq!(list.distance(two, four), Some(2));
+    /// assert_eq!(list.distance(four, two), None);
+    /// ```
+    pub fn distance(&self, from: Index<T>, to: Index<T>) -> Option<usize> {
+        self.get(from)?;
+        self.get(to)?;
+
+        let mut current = from;
+        let mut steps = 0;
+
+        while current != to {
+            // if we run off the end, `to` wasn't after `from`
+            current = self.next_index(current)?;
+            steps += 1;
+        }
+
+        Some(steps)
+    }
+
+    /// Removes every item that's equal to `value`, and returns how many were
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// Removing every one:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 1, 3, 1]);
+    ///
+    /// assert_eq!(list.remove_all_matching(&1), 3);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 3]);
+    /// ```
+    pub fn remove_all_matching(&mut self, value: &T) -> usize {
+        let mut removed = 0;
+        let mut next = self.head_index();
+
+        while let Some(index) = next {
+            // removing doesn't move any other items, so this stays valid
+            next = self.next_index(index);
+
+            if self[index] == *value {
+                self.remove(index);
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
+    /// Removes the items at positions `start..end` in the list, and returns
+    /// them in order.
+    ///
+    /// Positions count from the head, starting at zero. Like `range_mut`, an
+    /// `end` past the end of the list stops at the tail, and if `start` isn't
+    /// before `end`, nothing is removed.
+    ///
+    /// # Examples
+    ///
+    /// Removing a couple of items from the middle:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(list.drain_range(1, 3), vec![2, 3]);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 4, 5]);
+    /// ```
+    pub fn drain_range(&mut self, start: usize, end: usize) -> Vec<T> {
+        let mut drained = Vec::new();
+
+        if start >= end {
+            return drained;
+        }
+
+        let mut next = self.head_index();
+
+        for _ in 0..start {
+            next = next.and_then(|index| self.next_index(index));
+        }
+
+        for _ in start..end {
+            let index = match next {
+                Some(index) => index,
+                None => break,
+            };
+
+            next = self.next_index(index);
+
+            // we just walked to this index, so it's valid
+            drained.push(self.remove(index).unwrap());
+        }
+
+        drained
+    }
+
+    /// Removes the item at this index, and fills its slot with the item in
+    /// the last slot, so that the underlying vector shrinks by one instead of
+    /// leaving a hole.
+    ///
+    /// Returns the removed item, along with the new index of whichever item
+    /// was moved into its slot. The moved item's old index is no longer
+    /// valid. If the removed item was in the last slot, or the last slot was
+    /// already free, nothing is moved, and you get `None` instead.
+    ///
+    /// Returns `None` if the index isn't valid.
+    ///
+    /// # Examples
+    ///
+    /// Removing an item and following the one that moved:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    /// let fifteen = list.push_back(15);
+    ///
+    /// let (item, moved) = list.swap_remove_tracked(five).unwrap();
+    /// let moved = moved.unwrap();
+    ///
+    /// assert_eq!(item, 5);
+    /// assert_eq!(list.get(moved), Some(&15));
+    /// assert_eq!(list.get(fifteen), None);
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10, 15]);
+    /// ```
+    pub fn swap_remove_tracked(&mut self, index: Index<T>) -> Option<(T, Option<Index<T>>)> {
+        self.sweep();
+        self.get(index)?;
+
+        let slot = index.index;
+
+        self.detach(slot);
+
+        let removed = std::mem::replace(&mut self.contents[slot], Entry::Free { next_free: None });
+
+        let item = match removed {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => e.item,
+        };
+
+        // as with any removal, bump the generation so old indexes go stale
+        self.generation += 1;
+        self.len -= 1;
+
+        let last = self.contents.len() - 1;
+
+        let moved = if last == slot {
+            // we were at the end already, so there's nothing to move
+            self.contents.pop();
+
+            None
+        } else if let Entry::Occupied(_) = self.contents[last] {
+            let mut entry = match self.contents.pop() {
+                Some(Entry::Occupied(e)) => e,
+                _ => panic!("Corrupted list"),
+            };
+
+            // the moved item gets a fresh generation, so that its old index
+            // can't be used by mistake
+            entry.generation = self.generation;
+
+            match entry.prev {
+                Some(prev) => self.occupied_mut(prev).next = Some(slot),
+                None => self.head = Some(slot),
+            }
+
+            match entry.next {
+                Some(next) => self.occupied_mut(next).prev = Some(slot),
+                None => self.tail = Some(slot),
+            }
+
+            let moved = Index::new(slot, entry.generation);
+            self.contents[slot] = Entry::Occupied(entry);
+
+            Some(moved)
+        } else {
+            // the last slot is free, so moving it would mean digging it out of
+            // the free list; just free our slot like a normal removal instead
+            self.free_slot(slot);
+
+            None
+        };
+
+        self.trim_if_auto();
+
+        Some((item, moved))
+    }
+
+    /// Counts the items you reach by following the links from the head of
+    /// the list.
+    ///
+    /// This should always be the same as `len`; if it isn't, the list is
+    /// corrupted. The walk stops at a link to a missing or free slot, and
+    /// after more steps than there are slots, so it finishes even if the
+    /// links go around in a circle.
+    ///
+    /// # Examples
+    ///
+    /// A healthy list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(list.reachable_len(), list.len());
+    /// ```
+    pub fn reachable_len(&self) -> usize {
+        let mut count = 0;
+        let mut steps = 0;
+        let mut next = self.head;
+
+        while let Some(slot) = next {
+            if steps > self.contents.len() {
+                break;
+            }
+
+            steps += 1;
+
+            next = match self.contents.get(slot) {
+                Some(Entry::Occupied(e)) => {
+                    count += 1;
+                    e.next
+                }
+                // lazily removed items are still linked, but don't count
+                Some(Entry::Tombstone { next, .. }) => *next,
+                Some(Entry::Free { .. }) | None => break,
+            };
+        }
+
+        count
+    }
+
+    /// Inserts all of these items immediately before the provided index, in
+    /// order, and returns their indexes.
+    ///
+    /// Returns `None` and inserts nothing if the index isn't valid.
+    ///
+    /// # Examples
+    ///
+    /// Inserting several items at the front:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let one = list.push_back(1);
+    /// list.push_back(2);
+    ///
+    /// let indexes = list.insert_all_before(one, vec![7, 8]).unwrap();
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [7, 8, 1, 2]);
+    /// assert_eq!(list.head_index(), Some(indexes[0]));
+    /// ```
+    pub fn insert_all_before<I>(&mut self, index: Index<T>, items: I) -> Option<Vec<Index<T>>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.get(index)?;
+
+        // inserting each one right before the anchor keeps them in order
+        Some(
+            items
+                .into_iter()
+                .map(|item| self.insert_before(index, item).unwrap())
+                .collect(),
+        )
+    }
+
+    /// Returns mutable references to both the head and the tail of the list.
+    ///
+    /// Returns `None` if the list has fewer than two items, since the head
+    /// and the tail would be the same item.
+    ///
+    /// # Examples
+    ///
+    /// Moving a value from the tail to the head:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// if let Some((head, tail)) = list.head_tail_mut() {
+    ///     *head += *tail;
+    ///     *tail = 0;
+    /// }
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [4, 2, 0]);
+    /// ```
+    pub fn head_tail_mut(&mut self) -> Option<(&mut T, &mut T)> {
+        let (head, tail) = match (self.head_index(), self.tail_index()) {
+            (Some(head), Some(tail)) if head != tail => (head.index, tail.index),
+            _ => return None,
+        };
+
+        match get_two_mut(&mut self.contents, head, tail) {
+            (Entry::Occupied(head), Entry::Occupied(tail)) => {
+                Some((&mut head.item, &mut tail.item))
+            }
+            _ => panic!("Corrupted list"),
+        }
+    }
+
+    /// Calls a fallible closure on each item in the list, along with its
+    /// `Index`, stopping at the first error.
+    ///
+    /// If the closure returns an error, you get back the index of the item it
+    /// failed on, along with the error.
+    ///
+    /// # Examples
+    ///
+    /// Finding the first item that's too big:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// list.push_back(1);
+    /// let twenty = list.push_back(20);
+    /// list.push_back(30);
+    ///
+    /// let result = list.try_for_each_index(|_, &n| if n < 10 { Ok(()) } else { Err(n) });
+    ///
+    /// assert_eq!(result, Err((twenty, 20)));
+    /// ```
+    pub fn try_for_each_index<E, F>(&self, mut f: F) -> Result<(), (Index<T>, E)>
+    where
+        F: FnMut(Index<T>, &T) -> Result<(), E>,
+    {
+        for (index, item) in self.iter_with_indexes() {
+            f(index, item).map_err(|e| (index, e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls the closure on a mutable reference to each item in turn, and
+    /// removes the item if the closure returns true.
+    ///
+    /// The closure always gets to change the item first, so you can update
+    /// and prune the list in one pass.
+    ///
+    /// # Examples
+    ///
+    /// Doubling every item, and dropping the ones that get too big:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4]);
+    ///
+    /// list.for_each_mut_removing(|x| {
+    ///     *x *= 2;
+    ///     *x > 6
+    /// });
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 4, 6]);
+    /// ```
+    pub fn for_each_mut_removing<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut next = self.head_index();
+
+        while let Some(index) = next {
+            // removing doesn't move any other items, so this stays valid
+            next = self.next_index(index);
+
+            if f(&mut self[index]) {
+                self.remove(index);
+            }
+        }
+    }
+
+    /// Returns a reference to the item at this index, or the reason there
+    /// isn't one.
+    ///
+    /// This is like `get`, but tells you why the lookup failed, which is
+    /// handy for error messages.
+    ///
+    /// # Examples
+    ///
+    /// Telling a removed item apart from a live one:
+    ///
+    /// ```
+    /// use indexlist::{IndexError, IndexList};
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// let ten = list.push_back(10);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert_eq!(list.get_checked(ten), Ok(&10));
+    /// assert_eq!(list.get_checked(five), Err(IndexError::Freed));
+    /// ```
+    pub fn get_checked(&self, index: Index<T>) -> Result<&T, IndexError> {
+        match self.contents.get(index.index) {
+            None => Err(IndexError::OutOfBounds),
+            Some(Entry::Free { .. }) | Some(Entry::Tombstone { .. }) => Err(IndexError::Freed),
+            Some(Entry::Occupied(e)) if e.generation != index.generation => Err(IndexError::Stale),
+            Some(Entry::Occupied(e)) => Ok(&e.item),
+        }
+    }
+
+    /// Rebuilds the chain of free slots from scratch.
+    ///
+    /// Every free slot in the underlying storage is put back on the free
+    /// list, following the list's free strategy, and anything the old chain
+    /// said is thrown away. Ordinary use never needs this; it's for repairing
+    /// a list whose free chain has gotten out of step with its slots.
+    ///
+    /// # Examples
+    ///
+    /// Rebuilding a healthy list is harmless:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// list.push_back(10);
+    /// list.remove(five);
+    ///
+    /// list.rebuild_free_list();
+    ///
+    /// // the freed slot is still found, so this doesn't need to grow
+    /// assert!(list.try_push_back_in_place(15).is_ok());
+    /// ```
+    pub fn rebuild_free_list(&mut self) {
+        self.next_free = None;
+        self.free_tail = None;
+
+        for slot in 0..self.contents.len() {
+            if let Entry::Free { .. } = self.contents[slot] {
+                self.free_slot(slot);
+            }
+        }
+    }
+
+    /// Returns how fragmented the underlying vector is, from `0.0` for a list
+    /// with no holes up to nearly `1.0` for one that's mostly free.
+    ///
+    /// This is the same number as `free_ratio`, named for deciding when it's
+    /// worth calling `compact`. `free_slots` gives the raw count.
+    ///
+    /// # Examples
+    ///
+    /// Compacting once half the slots are free:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4]);
+    ///
+    /// list.pop_front();
+    /// list.pop_front();
+    ///
+    /// if list.fragmentation() >= 0.5 {
+    ///     list.compact();
+    /// }
+    ///
+    /// assert_eq!(list.fragmentation(), 0.0);
+    /// ```
+    pub fn fragmentation(&self) -> f64 {
+        self.free_ratio()
+    }
+
+    /// Swaps the items of this list with the items of another one.
+    ///
+    /// Unlike `std::mem::swap`, this doesn't carry the generation counters
+    /// across. Both lists move on to a generation newer than either of them
+    /// had, and every item in both is given it, so no `Index` from before the
+    /// swap will resolve in either list afterwards, not even to the item it
+    /// used to refer to. Each list keeps its own settings, like its free
+    /// strategy.
+    ///
+    /// # Examples
+    ///
+    /// Swapping, and then finding the items again:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut a = IndexList::from_slice(&[1, 2]);
+    /// let mut b = IndexList::from_slice(&[3]);
+    ///
+    /// let one = a.head_index().unwrap();
+    ///
+    /// a.swap_contents(&mut b);
+    ///
+    /// assert_eq!(a.iter().cloned().collect::<Vec<_>>(), [3]);
+    /// assert_eq!(b.iter().cloned().collect::<Vec<_>>(), [1, 2]);
+    ///
+    /// assert_eq!(a.get(one), None);
+    /// assert_eq!(b.get(one), None);
+    /// assert_eq!(b.index_of(&1).map(|i| b[i]), Some(1));
+    /// ```
+    pub fn swap_contents(&mut self, other: &mut IndexList<T>) {
+        std::mem::swap(&mut self.contents, &mut other.contents);
+        std::mem::swap(&mut self.head, &mut other.head);
+        std::mem::swap(&mut self.tail, &mut other.tail);
+        std::mem::swap(&mut self.next_free, &mut other.next_free);
+        std::mem::swap(&mut self.free_tail, &mut other.free_tail);
+        std::mem::swap(&mut self.len, &mut other.len);
+        std::mem::swap(&mut self.tombstones, &mut other.tombstones);
+
+        // every index either list has handed out has a generation no newer
+        // than its list's, so this makes all of them stale at once
+        let generation = std::cmp::max(self.generation, other.generation) + 1;
+
+        self.restamp(generation);
+        other.restamp(generation);
+    }
+
+    // moves the list to this generation, and gives it to every item
+    fn restamp(&mut self, generation: usize) {
+        self.generation = generation;
+
+        for entry in &mut self.contents {
+            if let Entry::Occupied(e) = entry {
+                e.generation = generation;
+            }
+        }
+    }
+
+    /// Consumes the list, and returns its items in a `Vec`, sorted from
+    /// smallest to largest.
+    ///
+    /// # Examples
+    ///
+    /// Finishing up with a sorted vector:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[3, 1, 2]);
+    ///
+    /// assert_eq!(list.into_sorted_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T>
+    where
+        T: Ord,
+    {
+        let mut items: Vec<T> = self.into_iter().collect();
+        items.sort();
+
+        items
+    }
+
+    /// Consumes the list, turns each item into any number of new items, and
+    /// returns a new list of all of them, in order.
+    ///
+    /// # Examples
+    ///
+    /// Expanding each item into two:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2]);
+    ///
+    /// let expanded = list.flat_map(|x| vec![x, x * 10]);
+    ///
+    /// assert_eq!(expanded.iter().cloned().collect::<Vec<_>>(), [1, 10, 2, 20]);
+    /// ```
+    pub fn flat_map<U, I, F>(self, mut f: F) -> IndexList<U>
+    where
+        U: PartialEq + std::fmt::Debug,
+        I: IntoIterator<Item = U>,
+        F: FnMut(T) -> I,
+    {
+        let mut list = IndexList::with_capacity(self.len());
+
+        for item in self {
+            for new in f(item) {
+                list.push_back(new);
+            }
+        }
+
+        list
+    }
+
+    /// Returns the index of every item in the list, in order, split into
+    /// groups of `size`.
+    ///
+    /// The last group has fewer than `size` indexes if they don't divide
+    /// evenly. Since these are indexes rather than references, you can still
+    /// change or remove items while working through the groups.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// Removing items a batch at a time:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+    ///
+    /// for chunk in list.chunk_indices(2) {
+    ///     list.remove(chunk[0]);
+    /// }
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 4]);
+    /// ```
+    pub fn chunk_indices(&self, size: usize) -> Vec<Vec<Index<T>>> {
+        assert!(size != 0, "chunk_indices: size must be non-zero");
+
+        self.index_snapshot()
+            .chunks(size)
+            .map(|chunk| chunk.to_vec())
+            .collect()
+    }
+
+    /// Returns the `NodeId` of the node at this index.
+    ///
+    /// See `NodeId` for when it stays valid. If the index itself isn't valid,
+    /// the `NodeId` won't resolve either.
+    ///
+    /// # Examples
+    ///
+    /// Getting an identity for a node:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    ///
+    /// let id = list.id(five);
+    ///
+    /// assert_eq!(list.resolve(id), Some(five));
+    /// ```
+    pub fn id(&self, index: Index<T>) -> NodeId<T> {
+        NodeId(index)
+    }
+
+    /// Returns the current `Index` of the node with this `NodeId`, if it's
+    /// still in the list.
+    ///
+    /// # Examples
+    ///
+    /// A removed node doesn't resolve:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// let five = list.push_back(5);
+    /// let id = list.id(five);
+    ///
+    /// list.remove(five);
+    ///
+    /// assert_eq!(list.resolve(id), None);
+    /// ```
+    pub fn resolve(&self, id: NodeId<T>) -> Option<Index<T>> {
+        self.get(id.0).map(|_| id.0)
+    }
+
+    /// Returns an iterator over the list that yields each item's position,
+    /// its `Index`, and a reference to it.
+    ///
+    /// Positions count from the head, starting at zero.
+    ///
+    /// # Examples
+    ///
+    /// Getting everything at once:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[10, 20, 30]);
+    ///
+    /// for (position, index, item) in list.enumerate_indexed() {
+    ///     assert_eq!(list[index], *item);
+    ///     assert_eq!(*item, (position + 1) * 10);
+    /// }
+    /// ```
+    pub fn enumerate_indexed(&self) -> impl Iterator<Item = (usize, Index<T>, &T)> {
+        self.iter_with_indexes()
+            .enumerate()
+            .map(|(position, (index, item))| (position, index, item))
+    }
+
+    /// Removes the item at this index, and returns it.
+    ///
+    /// This is the same as `remove`, but takes the index by reference, which
+    /// saves a dereference when you're going through a collection of them.
+    ///
+    /// # Examples
+    ///
+    /// Removing everything in a `Vec` of indexes:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// let indexes = list.index_snapshot();
+    ///
+    /// let removed: Vec<_> = indexes.iter().filter_map(|i| list.remove_ref(i)).collect();
+    ///
+    /// assert_eq!(removed, [1, 2, 3]);
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn remove_ref(&mut self, index: &Index<T>) -> Option<T> {
+        self.remove(*index)
+    }
+
+    /// Returns the index of the first item in the list, or a dangling index
+    /// if the list is empty.
+    ///
+    /// This is like `head_index`, but always gives you an `Index`. The
+    /// dangling one comes from `Index::dangling`, and never resolves.
+    ///
+    /// # Examples
+    ///
+    /// Looking up the head of an empty list:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::new();
+    ///
+    /// assert_eq!(list.get(list.head_index_or_dangling()), None);
+    ///
+    /// list.push_back(5);
+    ///
+    /// assert_eq!(list.get(list.head_index_or_dangling()), Some(&5));
+    /// ```
+    pub fn head_index_or_dangling(&self) -> Index<T> {
+        self.head_index().unwrap_or_else(Index::dangling)
+    }
+
+    /// Returns the index of the last item in the list, or a dangling index if
+    /// the list is empty.
+    ///
+    /// This is like `tail_index`, but always gives you an `Index`. The
+    /// dangling one comes from `Index::dangling`, and never resolves.
+    pub fn tail_index_or_dangling(&self) -> Index<T> {
+        self.tail_index().unwrap_or_else(Index::dangling)
+    }
+
+    /// Returns the number of distinct items in the list.
+    ///
+    /// # Examples
+    ///
+    /// Counting unique values:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 2, 3, 3, 3]);
+    ///
+    /// assert_eq!(list.count_distinct(), 3);
+    /// ```
+    pub fn count_distinct(&self) -> usize
+    where
+        T: Eq + std::hash::Hash,
+    {
+        self.iter().collect::<std::collections::HashSet<_>>().len()
+    }
+
+    /// Calls the closure on a mutable reference to each item at positions
+    /// `start..end` in the list.
+    ///
+    /// This follows the same rules for positions as `range_mut`: an `end`
+    /// past the end of the list stops at the tail, and if `start` isn't
+    /// before `end`, nothing happens.
+    ///
+    /// # Examples
+    ///
+    /// Adding to a couple of items in the middle:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+    ///
+    /// list.map_range(1, 3, |x| *x += 100);
+    ///
+    /// assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 102, 103, 4, 5]);
+    /// ```
+    pub fn map_range<F>(&mut self, start: usize, end: usize, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        let mut next = self.head_index();
+        let mut position = 0;
+
+        while let Some(index) = next {
+            if position >= end {
+                break;
+            }
+
+            next = self.next_index(index);
+
+            if position >= start {
+                f(&mut self[index]);
+            }
+
+            position += 1;
+        }
+    }
+
+    /// Returns the first item in the list, along with an iterator over the
+    /// rest of them, or `None` if the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// Splitting off the head:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// let (first, rest) = list.split_first().unwrap();
+    ///
+    /// assert_eq!(first, &1);
+    /// assert_eq!(rest.cloned().collect::<Vec<_>>(), [2, 3]);
+    /// ```
+    pub fn split_first(&self) -> Option<(&T, impl Iterator<Item = &T>)> {
+        let mut iter = self.iter();
+        let first = iter.next()?;
+
+        Some((first, iter))
+    }
+
+    /// Returns the last item in the list, along with an iterator over the
+    /// rest of them, or `None` if the list is empty.
+    ///
+    /// The rest come in their usual order, from the head.
+    ///
+    /// # Examples
+    ///
+    /// Splitting off the tail:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// let (last, rest) = list.split_last().unwrap();
+    ///
+    /// assert_eq!(last, &3);
+    /// assert_eq!(rest.cloned().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    pub fn split_last(&self) -> Option<(&T, impl Iterator<Item = &T>)> {
+        let last = self.peek_back()?;
+
+        Some((last, self.iter().take(self.len - 1)))
+    }
+
+    /// Returns the first `N` items in the list as an array, or `None` if
+    /// there are fewer than `N` of them.
+    ///
+    /// Any items past the first `N` are left out.
+    ///
+    /// # Examples
+    ///
+    /// Converting to arrays of different sizes:
+    ///
+    /// ```
+    /// use indexlist::IndexList;
+    ///
+    /// let list = IndexList::from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(list.to_array(), Some([1, 2, 3]));
+    /// assert_eq!(list.to_array(), Some([1, 2]));
+    /// assert_eq!(list.to_array::<4>(), None);
+    /// ```
+    pub fn to_array<const N: usize>(&self) -> Option<[T; N]>
+    where
+        T: Copy,
+    {
+        let items: Vec<T> = self.iter().take(N).cloned().collect();
+
+        std::convert::TryFrom::try_from(items).ok()
+    }
+}
+
+impl<T> IntoIterator for IndexList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let next_index = self.head;
+
+        IntoIter {
+            list: self,
+            next_index,
+        }
+    }
+}
+
+pub struct IntoIter<T> {
+    list: IndexList<T>,
+    next_index: Option<usize>,
+}
+
+impl<T> IntoIter<T> {
+    // yields the slot and entry of each item, for iterators that want more
+    // than just the item itself
+    fn next_entry(&mut self) -> Option<(usize, OccupiedEntry<T>)> {
+        loop {
+            let next_index = self.next_index?;
+            let entry = std::mem::replace(
+                &mut self.list.contents[next_index],
+                Entry::Free { next_free: None },
+            );
+
+            match entry {
+                Entry::Free { .. } => panic!("Corrupted list"),
+                // this was lazily removed, so there's nothing to yield
+                Entry::Tombstone { next, .. } => self.next_index = next,
+                Entry::Occupied(e) => {
+                    self.next_index = e.next;
+
+                    return Some((next_index, e));
+                }
+            }
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().map(|(_, e)| e.item)
+    }
+}
+
+struct Iter<'a, T>
+where
+    T: 'a,
+{
+    list: &'a IndexList<T>,
+    next_index: Option<usize>,
+    // how many items we've yielded so far, to catch cycles in debug builds
+    yielded: usize,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn new(list: &'a IndexList<T>) -> Iter<'a, T> {
+        Iter {
+            list,
+            next_index: list.head,
+            yielded: 0,
+        }
+    }
+
+    // yields the slot and entry of each item, for iterators that want more
+    // than just a reference to the item itself
+    fn next_entry(&mut self) -> Option<(usize, &'a OccupiedEntry<T>)> {
+        // do we have a next thing? we skip over anything lazily removed
+        let next_index = self.list.live_forward(self.next_index)?;
+
+        // what is it?
+        match &self.list.contents[next_index] {
+            Entry::Free { .. } | Entry::Tombstone { .. } => panic!("Corrupted list"),
+            Entry::Occupied(e) => {
+                // set up our next iteration
+                self.next_index = e.next;
+
+                // a list can't have more items than it has slots, so if we've
+                // yielded more than that, we're going around in circles
+                self.yielded += 1;
+                debug_assert!(
+                    self.yielded <= self.list.contents.len(),
+                    "cycle detected in IndexList"
+                );
+
+                Some((next_index, e))
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().map(|(_, e)| &e.item)
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            list: self.list,
+            next_index: self.next_index,
+            yielded: self.yielded,
+        }
+    }
+}
+
+impl<T> std::ops::Index<Index<T>> for IndexList<T>
+where
+    T: PartialEq,
+    T: std::fmt::Debug,
+{
+    type Output = T;
+
+    fn index(&self, index: Index<T>) -> &Self::Output {
+        match self.get(index) {
+            Some(item) => item,
+            None => panic!(
+                "IndexList: stale or out-of-bounds index (slot {}, generation {})",
+                index.index, index.generation
+            ),
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<Index<T>> for IndexList<T>
+where
+    T: PartialEq,
+    T: std::fmt::Debug,
+{
+    fn index_mut(&mut self, index: Index<T>) -> &mut Self::Output {
+        match self.get_mut(index) {
+            Some(item) => item,
+            None => panic!(
+                "IndexList: stale or out-of-bounds index (slot {}, generation {})",
+                index.index, index.generation
+            ),
+        }
+    }
+}
+
+// these compare the items in list order, so `assert_eq!(list, [1, 2, 3])`
+// works without collecting first
+
+impl<T> PartialEq<[T]> for IndexList<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        self.len == other.len() && Iter::new(self).eq(other.iter())
+    }
+}
+
+impl<'a, T> PartialEq<&'a [T]> for IndexList<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &&'a [T]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T> PartialEq<Vec<T>> for IndexList<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, const N: usize> PartialEq<[T; N]> for IndexList<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &[T; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+// returns mutable references to two different elements of a slice
+fn get_two_mut<T>(slice: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    assert!(a != b, "get_two_mut: indexes must be distinct");
+
+    if a < b {
+        let (left, right) = slice.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = slice.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_index() {
+        let index: Index<i32> = Index::new(1, 2);
+
+        assert_eq!(index.index, 1);
+        assert_eq!(index.generation, 2);
+    }
+
+    #[test]
+    fn create_list() {
+        let _list: IndexList<i32> = IndexList::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+
+        assert_eq!(
+            list.contents[0],
+            Entry::Occupied(OccupiedEntry {
+                item: 5,
+                next: None,
+                prev: None,
+                generation: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+
+        assert!(list.contains(&5));
+    }
+
+    #[test]
+    fn get() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let entry = list.get(five);
+
+        assert!(entry.is_some());
+
+        assert_eq!(entry.unwrap(), &5);
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let entry = list.get_mut(five);
+
+        assert!(entry.is_some());
+
+        assert_eq!(entry.unwrap(), &mut 5);
+    }
+
+    #[test]
+    fn next_index() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+        let _ten = list.push_back(10);
+
+        let ten_index = list.next_index(five).unwrap();
+
+        let ten_value = list.get(ten_index);
+
+        assert_eq!(ten_value.unwrap(), &10);
+        assert_eq!(None, list.next_index(ten_index));
+    }
+
+    #[test]
+    fn prev_index() {
+        let mut list = IndexList::new();
+
+        let _five = list.push_back(5);
+        let ten = list.push_back(10);
+
+        let five_index = list.prev_index(ten).unwrap();
+
+        let five_value = list.get(five_index);
+
+        assert_eq!(five_value.unwrap(), &5);
+        assert_eq!(None, list.prev_index(five_index));
+    }
+
+    #[test]
+    fn index() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let entry = list[five];
+
+        assert_eq!(entry, 5);
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let mut entry = list[five];
+
+        entry += 1;
+
+        let six = list.push_back(entry);
+
+        let new_entry = list[six];
+
+        assert_eq!(new_entry, 6);
+    }
+
+    #[test]
+    fn insert_thrice() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        list.push_back(10);
+        list.push_back(15);
+
+        assert_eq!(
+            list.contents[0],
+            Entry::Occupied(OccupiedEntry {
+                item: 5,
+                next: Some(1),
+                prev: None,
+                generation: 0,
+            })
+        );
+
+        assert_eq!(
+            list.contents[1],
+            Entry::Occupied(OccupiedEntry {
+                item: 10,
+                next: Some(2),
+                prev: Some(0),
+                generation: 0,
+            })
+        );
+
+        assert_eq!(
+            list.contents[2],
+            Entry::Occupied(OccupiedEntry {
+                item: 15,
+                next: None,
+                prev: Some(1),
+                generation: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn remove_middle() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+
+        let removed = list.remove(ten).unwrap();
+
+        assert_eq!(removed, 10);
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Occupied(OccupiedEntry {
+                        item: 5,
+                        next: Some(2),
+                        prev: None,
+                        generation: 0,
+                    }),
+                    Entry::Free { next_free: None },
+                    Entry::Occupied(OccupiedEntry {
+                        item: 15,
+                        next: None,
+                        prev: Some(0),
+                        generation: 0,
+                    }),
+                ],
+                generation: 1,
+                next_free: Some(1),
+                head: Some(0),
+                tail: Some(2),
+                len: 2,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: Some(1),
+            }
+        );
+    }
+
+    #[test]
+    fn remove_head() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+        list.push_back(10);
+        list.push_back(15);
+
+        let removed = list.remove(five).unwrap();
+
+        assert_eq!(removed, 5);
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Free { next_free: None },
+                    Entry::Occupied(OccupiedEntry {
+                        item: 10,
+                        next: Some(2),
+                        prev: None,
+                        generation: 0,
+                    }),
+                    Entry::Occupied(OccupiedEntry {
+                        item: 15,
+                        next: None,
+                        prev: Some(1),
+                        generation: 0,
+                    }),
+                ],
+                generation: 1,
+                next_free: Some(0),
+                head: Some(1),
+                tail: Some(2),
+                len: 2,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn remove_tail() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        list.push_back(10);
+        let fifteen = list.push_back(15);
+
+        let removed = list.remove(fifteen).unwrap();
+
+        assert_eq!(removed, 15);
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Occupied(OccupiedEntry {
+                        item: 5,
+                        next: Some(1),
+                        prev: None,
+                        generation: 0,
+                    }),
+                    Entry::Occupied(OccupiedEntry {
+                        item: 10,
+                        next: None,
+                        prev: Some(0),
+                        generation: 0,
+                    }),
+                    Entry::Free { next_free: None },
+                ],
+                generation: 1,
+                next_free: Some(2),
+                head: Some(0),
+                tail: Some(1),
+                len: 2,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn remove_only() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let removed = list.remove(five).unwrap();
+
+        assert_eq!(removed, 5);
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![Entry::Free { next_free: None },],
+                generation: 1,
+                next_free: Some(0),
+                head: None,
+                tail: None,
+                len: 0,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn remove_returns_none_when_not_there() {
+        let mut list = IndexList::new();
+
+        let five_index = list.push_back(5);
+
+        let five_entry = list.remove(five_index).unwrap();
+
+        assert_eq!(list.contents[0], Entry::Free { next_free: None });
+
+        assert_eq!(five_entry, 5);
+
+        assert!(list.remove(five_index).is_none());
+    }
+
+    #[test]
+    fn remove_at() {
+        let mut list = IndexList::new();
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        assert_eq!(list.remove_at(1), Some(2));
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 3]);
+
+        assert!(list.remove_at(2).is_none());
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+
+        list.remove(ten);
+
+        let mut iter = list.into_iter();
+
+        assert_eq!(iter.next().unwrap(), 5);
+        assert_eq!(iter.next().unwrap(), 15);
+
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn iter() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+
+        list.remove(ten);
+
+        let mut iter = list.iter();
+
+        assert_eq!(iter.next().unwrap(), &5);
+        assert_eq!(iter.next().unwrap(), &15);
+
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn iter_stride() {
+        let mut list = IndexList::new();
+
+        for i in 0..6 {
+            list.push_back(i);
+        }
+
+        assert_eq!(
+            list.iter_stride(2).copied().collect::<Vec<i32>>(),
+            vec![0, 2, 4]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn iter_stride_zero() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+
+        let _ = list.iter_stride(0);
+    }
+
+    #[test]
+    fn reallocation() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+
+        let ten = list.remove(ten).unwrap();
+
+        assert_eq!(ten, 10);
+
+        list.push_back(20);
+
+        assert_eq!(
+            list.contents[0],
+            Entry::Occupied(OccupiedEntry {
+                item: 5,
+                next: Some(2),
+                prev: None,
+                generation: 0,
+            })
+        );
+
+        assert_eq!(
+            list.contents[1],
+            Entry::Occupied(OccupiedEntry {
+                item: 20,
+                next: None,
+                prev: Some(2),
+                generation: 1,
+            })
+        );
+
+        assert_eq!(
+            list.contents[2],
+            Entry::Occupied(OccupiedEntry {
+                item: 15,
+                next: Some(1),
+                prev: Some(0),
+                generation: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn generations() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+
+        list.remove(ten);
+
+        let twenty = list.push_back(20);
+
+        // since we reallocate, that twenty should have gone where the ten was.
+        // this means that ten should now be invalid.
+        assert!(list.get(ten).is_none());
+
+        // however, five should be fine!
+        assert!(list.get(five).is_some());
+
+        // as should twenty!
+        assert!(list.get(twenty).is_some());
+    }
+
+    #[test]
+    fn head() {
+        let mut list = IndexList::new();
+
+        assert!(list.head().is_none());
+
+        let five = list.push_back(5);
+
+        assert_eq!(list.head().unwrap(), &5);
+
+        list.push_back(10);
+
+        list.remove(five);
+
+        assert_eq!(list.head().unwrap(), &10);
+
+        assert_eq!(list.contents[0], Entry::Free { next_free: None });
+
+        assert_eq!(list.head, Some(1));
+
+        assert_eq!(
+            list.contents[1],
+            Entry::Occupied(OccupiedEntry {
+                item: 10,
+                next: None,
+                prev: None,
+                generation: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn head_mut() {
+        let mut list = IndexList::new();
+
+        assert!(list.head_mut().is_none());
+
+        let five = list.push_back(5);
+
+        assert_eq!(list.head_mut().unwrap(), &mut 5);
+
+        list.push_back(10);
+
+        list.remove(five);
+
+        assert_eq!(list.head_mut().unwrap(), &mut 10);
+
+        assert_eq!(list.contents[0], Entry::Free { next_free: None });
+
+        assert_eq!(list.head, Some(1));
+
+        assert_eq!(
+            list.contents[1],
+            Entry::Occupied(OccupiedEntry {
+                item: 10,
+                next: None,
+                prev: None,
+                generation: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn head_index() {
+        let mut list = IndexList::new();
+
+        assert!(list.head_index().is_none());
+
+        let five = list.push_back(5);
+
+        assert_eq!(list.head_index().unwrap(), five);
+    }
+
+    #[test]
+    fn tail_index() {
+        let mut list = IndexList::new();
+
+        assert!(list.tail_index().is_none());
+
+        let _five = list.push_back(5);
+        let ten = list.push_back(10);
+
+        assert_eq!(list.tail_index().unwrap(), ten);
+    }
+
+    #[test]
+    fn push_front() {
+        let mut list = IndexList::new();
+
+        list.push_front(5);
+        list.push_front(10);
+        list.push_front(15);
+
+        assert_eq!(
+            list.contents[0],
+            Entry::Occupied(OccupiedEntry {
+                item: 5,
+                next: None,
+                prev: Some(1),
+                generation: 0,
+            })
+        );
+
+        assert_eq!(
+            list.contents[1],
+            Entry::Occupied(OccupiedEntry {
+                item: 10,
+                next: Some(0),
+                prev: Some(2),
+                generation: 0,
+            })
+        );
+
+        assert_eq!(
+            list.contents[2],
+            Entry::Occupied(OccupiedEntry {
+                item: 15,
+                next: Some(1),
+                prev: None,
+                generation: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn index_of() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        list.push_back(10);
+        list.push_back(15);
+
+        assert_eq!(list.index_of(&10).unwrap(), Index::new(1, 0));
+
+        assert!(list.index_of(&20).is_none());
+    }
+
+    #[test]
+    fn index_of_get_correct_generation() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.remove(ten);
+        list.push_back(15);
+
+        assert_eq!(
+            list.index_of(&5).unwrap(),
+            Index {
+                index: 0,
+                generation: 0,
+                _marker: PhantomData
+            }
+        );
+    }
+
+    #[test]
+    fn index_of_get_first_occurrence() {
+        let mut list = IndexList::new();
+
+        list.push_back(3);
+        let six = list.push_back(6);
+        let first_nine = list.push_back(9);
+        list.push_back(12);
+
+        list.remove(six);
+
+        let _second_nine = list.push_back(9);
+
+        assert_eq!(list.index_of(&9).unwrap(), first_nine);
+    }
+
+    #[test]
+    fn pop_front() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        list.push_back(10);
+        list.push_back(15);
+
+        assert_eq!(list.pop_front().unwrap(), 5);
+        assert_eq!(list.pop_front().unwrap(), 10);
+        assert_eq!(list.pop_front().unwrap(), 15);
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Free { next_free: None },
+                    Entry::Free { next_free: Some(0) },
+                    Entry::Free { next_free: Some(1) },
+                ],
+                generation: 3,
+                next_free: Some(2),
+                head: None,
+                tail: None,
+                len: 0,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        list.push_back(10);
+        list.push_back(15);
+
+        assert_eq!(list.pop_front().unwrap(), 5);
+        assert_eq!(list.pop_front().unwrap(), 10);
+        assert_eq!(list.pop_front().unwrap(), 15);
+
+        list.push_back(5);
+        list.push_back(10);
+        list.push_back(15);
+
+        assert_eq!(list.pop_front().unwrap(), 5);
+        assert_eq!(list.pop_front().unwrap(), 10);
+        assert_eq!(list.pop_front().unwrap(), 15);
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Free { next_free: Some(1) },
+                    Entry::Free { next_free: Some(2) },
+                    Entry::Free { next_free: None },
+                ],
+                generation: 6,
+                next_free: Some(0),
+                head: None,
+                tail: None,
+                len: 0,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn push_front_next_free() {
+        let mut list = IndexList::new();
+
+        list.push_front(0);
+        list.push_front(73);
+        list.pop_front();
+
+        list.push_front(1);
+        list.push_front(2);
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Occupied(OccupiedEntry {
+                        item: 0,
+                        next: None,
+                        prev: Some(1),
+                        generation: 0
+                    }),
+                    Entry::Occupied(OccupiedEntry {
+                        item: 1,
+                        next: Some(0),
+                        prev: Some(2),
+                        generation: 1
+                    }),
+                    Entry::Occupied(OccupiedEntry {
+                        item: 2,
+                        next: Some(1),
+                        prev: None,
+                        generation: 1
+                    })
+                ],
+                generation: 1,
+                next_free: None,
+                head: Some(2),
+                tail: Some(0),
+                len: 3,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: None,
+            }
+        );
+    }
+
+    #[test]
+    fn insert_before() {
+        let mut list = IndexList::new();
+
+        let index = list.push_front(2);
+        list.insert_before(index, 0);
+
+        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 2]);
+        assert_eq!(*list.get(list.prev_index(index).unwrap()).unwrap(), 0);
+
+        list.insert_before(index, 1);
+
+        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 1, 2]);
+        assert_eq!(*list.get(list.prev_index(index).unwrap()).unwrap(), 1);
+    }
+
+    #[test]
+    fn insert_after() {
+        let mut list = IndexList::new();
+
+        let index = list.push_front(0);
+        list.insert_after(index, 2);
+
+        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 2]);
+        assert_eq!(*list.get(list.next_index(index).unwrap()).unwrap(), 2);
+
+        list.insert_after(index, 1);
+
+        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 1, 2]);
+        assert_eq!(*list.get(list.next_index(index).unwrap()).unwrap(), 1);
+    }
+
+    #[test]
+    fn compact() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+        let twenty = list.push_back(20);
+        list.push_front(0);
+
+        list.remove(ten);
+        list.remove(twenty);
+
+        list.compact();
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Occupied(OccupiedEntry {
+                        item: 0,
+                        next: Some(1),
+                        prev: None,
+                        generation: 3,
+                    }),
+                    Entry::Occupied(OccupiedEntry {
+                        item: 5,
+                        next: Some(2),
+                        prev: Some(0),
+                        generation: 3,
+                    }),
+                    Entry::Occupied(OccupiedEntry {
+                        item: 15,
+                        next: None,
+                        prev: Some(1),
+                        generation: 3,
+                    }),
+                ],
+                generation: 3,
+                next_free: None,
+                head: Some(0),
+                tail: Some(2),
+                len: 3,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: None,
+            }
+        );
+    }
+
+    #[test]
+    fn as_slice() {
+        let mut list = IndexList::new();
+
+        let indexes: Vec<_> = (0..6).map(|i| list.push_back(i)).collect();
+        list.push_front(10);
+
+        list.remove(indexes[1]);
+        list.remove(indexes[4]);
+        list.move_before(indexes[5], indexes[0]);
+
+        let expected: Vec<i32> = list.iter().copied().collect();
+
+        assert_eq!(list.as_slice(), expected.iter().collect::<Vec<_>>());
+        assert_eq!(list.contents.len(), 5);
+        assert_eq!(list.get(indexes[0]), None);
+
+        assert!(IndexList::<i32>::new().as_slice().is_empty());
+    }
+
+    #[test]
+    fn compact_empty() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+        list.remove(five);
+
+        list.compact();
+
+        assert!(list.contents.is_empty());
+        assert!(list.head().is_none());
+
+        list.push_back(10);
+
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![10]);
+    }
+
+    #[test]
+    fn free_ratio() {
+        let mut list = IndexList::new();
+
+        assert_eq!(list.free_ratio(), 0.0);
+
+        let five = list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+        list.push_back(20);
+
+        list.remove(five);
+        list.remove(ten);
+
+        assert_eq!(list.free_ratio(), 0.5);
+    }
+
+    #[test]
+    fn trim_trailing_free() {
+        let mut list = IndexList::new();
+
+        let zero = list.push_back(0);
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let three = list.push_back(3);
+        let four = list.push_back(4);
+
+        list.remove(one);
+        list.remove(four);
+        list.remove(three);
+
+        list.trim_trailing_free();
+
+        assert_eq!(list.contents.len(), 3);
+        assert_eq!(list.next_free, Some(1));
+        assert_eq!(list.contents[1], Entry::Free { next_free: None });
+
+        assert_eq!(list.get(zero), Some(&0));
+        assert_eq!(list.get(two), Some(&2));
+
+        // the hole in the middle is reused first, then we grow again
+        let five = list.push_back(5);
+        let six = list.push_back(6);
+
+        assert_eq!(five.index, 1);
+        assert_eq!(six.index, 3);
+        assert!(list.get(three).is_none());
+
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![0, 2, 5, 6]);
+    }
+
+    #[test]
+    fn pop_front_while() {
+        let mut list = IndexList::new();
+
+        list.push_back(2);
+        list.push_back(4);
+        list.push_back(5);
+        list.push_back(6);
+
+        assert_eq!(list.pop_front_while(|n| n % 2 == 0), vec![2, 4]);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![5, 6]);
+
+        assert!(list.pop_front_while(|n| n % 2 == 0).is_empty());
+
+        assert_eq!(list.pop_front_while(|_| true), vec![5, 6]);
+        assert!(list.head().is_none());
+    }
+
+    #[test]
+    fn cast_index() {
+        let index: Index<i32> = Index::new(1, 2);
+
+        let cast: Index<u32> = index.cast();
+
+        assert_eq!(cast.index, 1);
+        assert_eq!(cast.generation, 2);
+
+        let back: Index<i32> = cast.cast();
+
+        assert_eq!(back, index);
+    }
+
+    #[test]
+    fn cycle() {
+        let mut list = IndexList::new();
+
+        assert!(list.cycle().next().is_none());
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        assert_eq!(
+            list.cycle().take(7).copied().collect::<Vec<i32>>(),
+            vec![1, 2, 3, 1, 2, 3, 1]
+        );
+    }
+
+    #[test]
+    fn splice() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        list.push_back(3);
+        let four = list.push_back(4);
+
+        assert_eq!(list.splice(two, Some(four), vec![9]), vec![2, 3]);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 9, 4]);
+
+        // the removed slots are reused
+        assert_eq!(list.contents.len(), 4);
+
+        // `to` comes before `from`, so nothing happens
+        assert!(list.splice(four, Some(one), vec![7]).is_empty());
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 9, 4]);
+
+        // an empty range is just an insertion
+        assert!(list.splice(four, Some(four), vec![5, 6]).is_empty());
+        assert_eq!(
+            list.iter().copied().collect::<Vec<i32>>(),
+            vec![1, 9, 5, 6, 4]
+        );
+
+        assert_eq!(list.splice(one, None, vec![0]), vec![1, 9, 5, 6, 4]);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![0]);
+
+        // stale indexes are ignored
+        assert!(list.splice(two, None, vec![7]).is_empty());
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![0]);
+    }
+
+    #[test]
+    fn index_is_copy_for_any_item() {
+        let mut list = IndexList::new();
+
+        let hello = list.push_back(String::from("hello"));
+        let copy = hello;
+
+        assert_eq!(list.get(hello), list.get(copy));
+    }
+
+    #[test]
+    fn get_or_default() {
+        let mut list = IndexList::new();
+
+        let hello = list.push_back(String::from("hello"));
+        let world = list.push_back(String::from("world"));
+
+        list.remove(hello);
+
+        assert_eq!(list.get_or_default(hello), String::new());
+        assert_eq!(list.get_or_default(world), "world");
+    }
+
+    #[test]
+    #[should_panic(expected = "stale or out-of-bounds index (slot 0, generation 0)")]
+    fn index_stale() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+        list.remove(five);
+
+        let _ = list[five];
+    }
+
+    #[test]
+    #[should_panic(expected = "stale or out-of-bounds index (slot 3, generation 0)")]
+    fn index_mut_out_of_bounds() {
+        let mut list: IndexList<i32> = IndexList::new();
+
+        list[Index::new(3, 0)] = 5;
+    }
+
+    #[test]
+    fn iter_slots() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        list.remove(one);
+        list.push_back(4);
+
+        assert_eq!(
+            list.iter_slots().collect::<Vec<(usize, &i32)>>(),
+            vec![(1, &2), (2, &3), (0, &4)]
+        );
+    }
+
+    #[test]
+    fn prepend() {
+        let mut list = IndexList::new();
+        list.push_back(3);
+        list.push_back(4);
+
+        let mut other = IndexList::new();
+        let one = other.push_back(1);
+        other.push_back(2);
+
+        list.prepend(&mut other);
+
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 2, 3, 4]);
+        assert!(other.head().is_none());
+
+        // the old index doesn't see new items in other
+        other.push_back(5);
+        assert!(other.get(one).is_none());
+
+        // prepending onto an empty list works too
+        let mut empty = IndexList::new();
+        empty.prepend(&mut list);
+
+        assert_eq!(
+            empty.iter().copied().collect::<Vec<i32>>(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn len() {
+        let mut list = IndexList::new();
+
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        let five = list.push_back(5);
+        list.push_front(0);
+        list.insert_before(five, 3);
+        list.insert_after(five, 7);
+
+        assert_eq!(list.len(), 4);
+
+        list.remove(five);
+        list.pop_front();
+
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+    }
+
+    #[test]
+    fn slot_counts() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+
+        list.remove(ten);
+
+        assert_eq!(list.free_slots(), 1);
+        assert_eq!(list.occupied_slots(), 2);
+
+        list.push_back(20);
+
+        assert_eq!(list.free_slots(), 0);
+        assert_eq!(list.occupied_slots(), 3);
+    }
+
+    #[test]
+    fn pop_back() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        list.push_back(10);
+
+        assert_eq!(list.pop_back(), Some(10));
+        assert_eq!(list.pop_back(), Some(5));
+        assert_eq!(list.pop_back(), None);
+    }
+
+    #[test]
+    fn auto_trim() {
+        let mut list = IndexList::new().with_auto_trim(true);
+
+        for _ in 0..10 {
+            for i in 0..100 {
+                list.push_back(i);
+            }
+
+            assert!(list.capacity() <= 128);
+
+            while list.len() > 1 {
+                list.pop_back();
+            }
+
+            assert!(list.capacity() < 10);
+        }
+
+        // without auto trim, the slots stick around
+        let mut list = IndexList::new();
+
+        for i in 0..100 {
+            list.push_back(i);
+        }
+
+        while list.len() > 1 {
+            list.pop_back();
+        }
+
+        assert_eq!(list.contents.len(), 100);
+    }
+
+    #[test]
+    fn auto_trim_queue() {
+        let mut list = IndexList::new().with_auto_trim(true);
+
+        for i in 0..100 {
+            list.push_back(i);
+        }
+
+        // a queue reuses the slot it just freed, so it never needs to grow
+        for i in 100..1000 {
+            list.push_back(i);
+            assert_eq!(list.pop_front(), Some(i - 100));
+        }
+
+        assert!(list.contents.len() <= 101);
+
+        // draining it from the front frees slots all over, but once it's
+        // empty they all go
+        while list.len() > 1 {
+            list.pop_front();
+        }
+
+        assert!(list.contents.len() <= 101);
+
+        list.pop_front();
+
+        assert!(list.contents.is_empty());
+        assert!(list.capacity() < 10);
+
+        // and it keeps working afterwards
+        for i in 0..10 {
+            list.push_back(i);
+        }
+
+        for i in 0..10 {
+            assert_eq!(list.pop_front(), Some(i));
+        }
+
+        assert!(list.contents.is_empty());
+    }
+
+    #[test]
+    fn auto_trim_keeps_indexes() {
+        let mut list = IndexList::new().with_auto_trim(true);
+
+        let indexes: Vec<_> = (0..10).map(|i| list.push_back(i)).collect();
+
+        for &index in &indexes[1..] {
+            list.remove(index);
+        }
+
+        assert_eq!(list.contents.len(), 1);
+        assert_eq!(list.get(indexes[0]), Some(&0));
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![0]);
+    }
+
+    #[test]
+    fn get_pair_mut() {
+        let mut list = IndexList::new();
+
+        let ten = list.push_back(10);
+        let twenty = list.push_back(20);
+
+        {
+            let (a, b) = list.get_pair_mut(ten).unwrap();
+            let average = (*a + *b) / 2;
+            *a = average;
+            *b = average;
+        }
+
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![15, 15]);
+
+        // the tail has nothing after it
+        assert!(list.get_pair_mut(twenty).is_none());
+
+        // and stale indexes don't work
+        list.remove(ten);
+        list.push_back(5);
+        assert!(list.get_pair_mut(ten).is_none());
+
+        // the successor can come before us in the vector, too
+        let (a, b) = list.get_pair_mut(twenty).unwrap();
+        assert_eq!((*a, *b), (15, 5));
+    }
+
+    #[test]
+    fn singleton() {
+        let list = IndexList::singleton(5);
+
+        assert_eq!(list.head(), Some(&5));
+        assert_eq!(list.len(), 1);
+
+        let (list, five) = IndexList::singleton_with_index(5);
+
+        assert_eq!(list.get(five), Some(&5));
+        assert_eq!(list.head_index(), list.tail_index());
+    }
+
+    #[test]
+    fn push_front_all_ordered() {
+        let mut list = IndexList::new();
+
+        list.push_back(4);
+        list.push_back(5);
+
+        let indexes = list.push_front_all_ordered(vec![1, 2, 3]);
+
+        assert_eq!(
+            list.iter().copied().collect::<Vec<i32>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            indexes.into_iter().map(|i| list[i]).collect::<Vec<i32>>(),
+            vec![1, 2, 3]
+        );
+
+        let mut empty = IndexList::new();
+        empty.push_front_all_ordered(vec![1, 2]);
+
+        assert_eq!(empty.iter().copied().collect::<Vec<i32>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn all_indexes_of() {
+        let mut list = IndexList::new();
+
+        for &i in &[1, 2, 1, 3, 1] {
+            list.push_back(i);
+        }
+
+        let ones = list.all_indexes_of(&1);
+
+        assert_eq!(ones.len(), 3);
+        assert!(ones.iter().all(|&index| list.get(index) == Some(&1)));
+
+        assert!(list.all_indexes_of(&4).is_empty());
+    }
+
+    #[test]
+    fn swap_with_next() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let three = list.push_back(3);
+
+        assert!(list.swap_with_next(one));
+
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![2, 1, 3]);
+        assert_eq!(list.get(one), Some(&1));
+        assert_eq!(list.get(two), Some(&2));
+        assert_eq!(list.head_index(), Some(two));
+
+        assert!(list.swap_with_next(one));
+
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![2, 3, 1]);
+        assert_eq!(list.tail_index(), Some(one));
+        assert_eq!(list.prev_index(one), Some(three));
+
+        // the tail has nothing to swap with
+        assert!(!list.swap_with_next(one));
+    }
+
+    #[test]
+    fn swap_with_prev() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+
+        assert!(list.swap_with_prev(two));
+
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![2, 1]);
+        assert_eq!(list.head_index(), Some(two));
+        assert_eq!(list.tail_index(), Some(one));
+
+        assert!(!list.swap_with_prev(two));
+
+        list.remove(one);
+        assert!(!list.swap_with_prev(one));
+    }
+
+    #[test]
+    fn drain_if_indexed() {
+        let mut list = IndexList::new();
+
+        let indexes: Vec<_> = (1..=5).map(|i| list.push_back(i)).collect();
+        let targets = [indexes[0], indexes[2], indexes[4]];
+
+        let drained = list.drain_if_indexed(|index, _| targets.contains(&index));
+
+        assert_eq!(drained, vec![1, 3, 5]);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![2, 4]);
+
+        let drained = list.drain_if_indexed(|_, &item| item > 2);
+
+        assert_eq!(drained, vec![4]);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![2]);
+    }
+
+    #[test]
+    fn try_push_back_in_place() {
+        let mut list = IndexList::new();
+
+        for i in 0..3 {
+            list.push_back(i);
+        }
+
+        assert_eq!(list.try_push_back_in_place(3), Err(3));
+
+        list.pop_front();
+
+        let three = list.try_push_back_in_place(3).unwrap();
+
+        assert_eq!(three.index, 0);
+        assert_eq!(list.try_push_back_in_place(4), Err(4));
+
+        assert_eq!(list.contents.len(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn try_push_back_in_place_after_remove_lazy() {
+        let mut list = IndexList::new();
+
+        let zero = list.push_back(0);
+        list.push_back(1);
+
+        list.remove_lazy(zero);
+
+        let two = list.try_push_back_in_place(2).unwrap();
+
+        assert_eq!(two.index, 0);
+        assert_eq!(list.try_push_back_in_place(3), Err(3));
+        assert_eq!(list.contents.len(), 2);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn iter_copied() {
+        let mut list = IndexList::new();
+
+        list.push_back(5);
+        let ten = list.push_back(10);
+        list.push_back(15);
+
+        list.remove(ten);
+
+        assert_eq!(list.iter_copied().collect::<Vec<i32>>(), vec![5, 15]);
+    }
+
+    #[test]
+    fn remove_lazy() {
+        let mut list = IndexList::new();
+
+        let indexes: Vec<_> = (0..5).map(|i| list.push_back(i)).collect();
+
+        assert_eq!(list.remove_lazy(indexes[0]), Some(0));
+        assert_eq!(list.remove_lazy(indexes[2]), Some(2));
+        assert_eq!(list.remove_lazy(indexes[3]), Some(3));
+        assert_eq!(list.remove_lazy(indexes[4]), Some(4));
+
+        // stale indexes are rejected
+        assert!(list.remove_lazy(indexes[2]).is_none());
+        assert!(list.get(indexes[2]).is_none());
+
+        // everything that reads the list skips the tombstones
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1]);
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.head_index(), Some(indexes[1]));
+        assert_eq!(list.tail_index(), Some(indexes[1]));
+        assert!(list.next_index(indexes[1]).is_none());
+        assert!(list.prev_index(indexes[1]).is_none());
+        assert_eq!(list.index_of(&1), Some(indexes[1]));
+        assert!(!list.contains(&3));
+        assert_eq!(list.len(), 1);
+
+        list.sweep();
+
+        assert_eq!(
+            list,
+            IndexList {
+                contents: vec![
+                    Entry::Free { next_free: None },
+                    Entry::Occupied(OccupiedEntry {
+                        item: 1,
+                        next: None,
+                        prev: None,
+                        generation: 0,
+                    }),
+                    Entry::Free { next_free: Some(0) },
+                    Entry::Free { next_free: Some(2) },
+                    Entry::Free { next_free: Some(3) },
+                ],
+                generation: 4,
+                next_free: Some(4),
+                head: Some(1),
+                tail: Some(1),
+                len: 1,
+                tombstones: 0,
+                auto_trim: false,
+                removals_since_trim: 0,
+                free_strategy: FreeStrategy::Lifo,
+                free_tail: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn remove_lazy_then_mutate() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        list.push_back(3);
+
+        list.remove_lazy(two);
+
+        assert_eq!(list.next_index(one).map(|i| list[i]), Some(3));
+
+        // pushing sweeps first, and then reuses the slot
+        let four = list.push_back(4);
+
+        assert_eq!(four.index, 1);
+        assert_eq!(list.tombstones, 0);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 3, 4]);
+
+        list.remove_lazy(one);
+
+        assert_eq!(list.into_iter().collect::<Vec<i32>>(), vec![3, 4]);
+    }
+
+    #[test]
+    fn reserve_exact() {
+        let mut list: IndexList<i32> = IndexList::with_capacity(0);
+
+        list.reserve_exact(10);
+
+        assert_eq!(list.capacity(), 10);
+
+        for i in 0..10 {
+            list.push_back(i);
+        }
+
+        assert_eq!(list.capacity(), 10);
+    }
+
+    #[test]
+    fn get_disjoint_mut() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let three = list.push_back(3);
+        let four = list.push_back(4);
+
+        {
+            let items = list.get_disjoint_mut(&[four, one, two]).unwrap();
+
+            assert_eq!(items.len(), 3);
+
+            for (item, value) in items.into_iter().zip(vec![40, 10, 20]) {
+                *item = value;
+            }
+        }
+
+        assert_eq!(
+            list.iter().copied().collect::<Vec<i32>>(),
+            vec![10, 20, 3, 40]
+        );
+
+        assert!(list.get_disjoint_mut(&[one, three, one]).is_none());
+
+        list.remove(three);
+        assert!(list.get_disjoint_mut(&[one, three]).is_none());
+
+        assert!(list.get_disjoint_mut(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn count_valid() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+        let ten = list.push_back(10);
+        let fifteen = list.push_back(15);
+
+        list.remove(ten);
+        list.push_back(20);
+
+        assert_eq!(list.count_valid(&[five, ten, fifteen, five]), 3);
+        assert_eq!(list.count_valid(&[]), 0);
+    }
+
+    #[test]
+    fn count_valid_without_partial_eq() {
+        struct Opaque;
+
+        let mut list: IndexList<Opaque> = IndexList::default();
+
+        list.contents.push(Entry::Occupied(OccupiedEntry {
+            item: Opaque,
+            generation: 0,
+            next: None,
+            prev: None,
+        }));
+
+        let live = Index::new(0, 0);
+        let stale = Index::new(0, 1);
+        let missing = Index::new(1, 0);
+
+        assert_eq!(list.count_valid(&[live, stale, missing]), 1);
+    }
+
+    #[test]
+    fn try_insert_before() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let zero = list.try_insert_before(five, 0).unwrap();
+
+        assert_eq!(list.get(zero), Some(&0));
+        assert_eq!(list.head(), Some(&0));
+
+        list.remove(five);
+
+        assert_eq!(
+            list.try_insert_before(five, 1),
+            Err((InsertError::SlotFree, 1))
+        );
+
+        list.push_back(10);
+
+        assert_eq!(
+            list.try_insert_before(five, 1),
+            Err((InsertError::StaleGeneration, 1))
+        );
+
+        assert_eq!(
+            list.try_insert_before(Index::new(5, 0), 1),
+            Err((InsertError::OutOfBounds, 1))
+        );
+    }
+
+    #[test]
+    fn try_insert_after() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let six = list.try_insert_after(five, 6).unwrap();
+
+        assert_eq!(list.tail_index(), Some(six));
+
+        list.remove(five);
+
+        assert_eq!(
+            list.try_insert_after(five, 1),
+            Err((InsertError::SlotFree, 1))
+        );
+        assert_eq!(
+            list.try_insert_after(Index::new(5, 0), 1),
+            Err((InsertError::OutOfBounds, 1))
+        );
+    }
+
+    #[test]
+    fn head_entry_mut() {
+        let mut list = IndexList::new();
+
+        assert!(list.head_entry_mut().is_none());
+
+        list.push_back(1);
+        list.push_back(2);
+
+        let (index, head) = list.head_entry_mut().unwrap();
+        *head = 10;
+
+        assert_eq!(list.get(index), Some(&10));
+        assert_eq!(list.head_index(), Some(index));
+    }
+
+    #[test]
+    fn tail_entry_mut() {
+        let mut list = IndexList::new();
+
+        assert!(list.tail_entry_mut().is_none());
+
+        list.push_back(1);
+        list.push_back(2);
+
+        let (index, tail) = list.tail_entry_mut().unwrap();
+        *tail = 20;
+
+        assert_eq!(list.get(index), Some(&20));
+        assert_eq!(list.tail_index(), Some(index));
+    }
+
+    #[test]
+    fn repeat() {
+        let list = IndexList::repeat(7, 3);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [7, 7, 7]);
+
+        let empty = IndexList::repeat(7, 0);
+
+        assert!(empty.is_empty());
+        assert_eq!(empty.head(), None);
+    }
+
+    #[test]
+    fn zip_with() {
+        let mut list = IndexList::new();
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let mut other = IndexList::new();
+
+        other.push_back(10);
+        other.push_back(20);
+
+        let sums = list.zip_with(other, |a, b| a + b);
+
+        assert_eq!(sums.len(), 2);
+        assert_eq!(sums.iter().cloned().collect::<Vec<_>>(), [11, 22]);
+    }
+
+    #[test]
+    fn fold_indexed() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let zero = list.push_front(0);
+
+        let pairs = list.fold_indexed(Vec::new(), |mut acc, index, &item| {
+            acc.push((index, item));
+            acc
+        });
+
+        assert_eq!(pairs, vec![(zero, 0), (one, 1), (two, 2)]);
+    }
+
+    #[test]
+    fn split_off_tracked() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let three = list.push_back(3);
+        list.push_back(4);
+
+        let (back, head, tail) = list.split_off_tracked(three).unwrap();
+
+        assert_eq!(back.get(head), Some(&3));
+        assert_eq!(back.get(tail), Some(&4));
+        assert_eq!(back.len(), 2);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.head_index(), Some(one));
+        assert_eq!(list.tail_index(), Some(two));
+        assert_eq!(list.get(three), None);
+
+        assert!(list.split_off_tracked(three).is_none());
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn pop_back_while() {
+        let mut list = IndexList::new();
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(8);
+        list.push_back(9);
+
+        assert_eq!(list.pop_back_while(|&x| x > 5), vec![9, 8]);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2]);
+
+        assert_eq!(list.pop_back_while(|&x| x > 5), vec![]);
+        assert_eq!(list.pop_back_while(|_| true), vec![2, 1]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn rebind() {
+        let mut a = IndexList::new();
+
+        let one = a.push_back(1);
+        let two = a.push_back(2);
+
+        let mut b = IndexList::new();
+
+        let ten = b.push_back(10);
+        b.remove(ten);
+        let eleven = b.push_back(11);
+
+        std::mem::swap(&mut a, &mut b);
+
+        // the generations differ, so the old index doesn't work as-is
+        assert_eq!(a.get(one), None);
+        assert_eq!(a.rebind(one), Some(eleven));
+        assert_eq!(a.rebind(two), None);
+        assert_eq!(b.rebind(one), Some(one));
+        assert_eq!(b.rebind(two), Some(two));
+
+        a.remove(eleven);
+
+        assert_eq!(a.rebind(one), None);
+    }
+
+    #[test]
+    fn get_cloned() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let copy = list.get_cloned(five).unwrap();
+
+        // the list isn't borrowed any more, so we can change it
+        list[five] = 6;
+        list.push_back(copy);
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [6, 5]);
+
+        list.remove(five);
+
+        assert_eq!(list.get_cloned(five), None);
+    }
+
+    #[test]
+    fn iter_mut_indexed() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        list.push_back(2);
+        let zero = list.push_front(0);
+
+        let mut seen = Vec::new();
+
+        for (index, item) in list.iter_mut_indexed() {
+            *item += 10;
+            seen.push(index);
+        }
+
+        assert_eq!(seen[0], zero);
+        assert_eq!(seen[1], one);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10, 11, 12]);
+
+        assert_eq!(list.remove(seen[1]), Some(11));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10, 12]);
+    }
+
+    #[test]
+    fn append_preserving() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        list.remove(two);
+
+        let mut other = IndexList::new();
+
+        let zero = other.push_back(0);
+        other.push_back(3);
+        other.remove(zero);
+        other.push_back(4);
+        other.push_back(5);
+
+        let moved = list.append_preserving(other);
+
+        assert_eq!(moved.len(), 3);
+        assert_eq!(list.get(moved[0]), Some(&3));
+        assert_eq!(list.get(moved[1]), Some(&4));
+        assert_eq!(list.get(moved[2]), Some(&5));
+        assert_eq!(list.get(one), Some(&1));
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.tail_index(), Some(moved[2]));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 3, 4, 5]);
+
+        // both lists' free slots can be reused
+        let six = list.push_back(6);
+        let seven = list.push_back(7);
+
+        assert_eq!(list.free_slots(), 0);
+        assert_eq!(list.get(six), Some(&6));
+        assert_eq!(list.get(seven), Some(&7));
+
+        assert_eq!(list.remove(moved[1]), Some(4));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 3, 5, 6, 7]);
+    }
+
+    #[test]
+    fn append_preserving_into_empty() {
+        let mut list = IndexList::new();
+
+        let mut other = IndexList::new();
+
+        other.push_back(1);
+        other.push_back(2);
+
+        let moved = list.append_preserving(other);
+
+        assert_eq!(list.head_index(), Some(moved[0]));
+        assert_eq!(list.tail_index(), Some(moved[1]));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2]);
+
+        assert!(list.append_preserving(IndexList::new()).is_empty());
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn append_preserving_keeps_stale_indexes_stale() {
+        let mut other = IndexList::new();
+        other.push_back(99);
+
+        // `take` leaves an empty list behind, so the moved item lands in the
+        // slot that `one` points at
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        list.take();
+
+        let moved = list.append_preserving(other);
+
+        assert_eq!(moved[0].index, one.index);
+        assert_eq!(list.get(one), None);
+        assert_eq!(list.get(moved[0]), Some(&99));
+
+        // `swap_remove_tracked` moves `b` out of the last slot, which is
+        // where the appended item goes
+        let mut other = IndexList::new();
+        other.push_back(99);
+
+        let mut list = IndexList::new();
+
+        let a = list.push_back(1);
+        let b = list.push_back(2);
+
+        let (_, b_now) = list.swap_remove_tracked(a).unwrap();
+        let moved = list.append_preserving(other);
+
+        assert_eq!(moved[0].index, b.index);
+        assert_eq!(list.get(b), None);
+        assert_eq!(list.get(b_now.unwrap()), Some(&2));
+        assert_eq!(list.get(moved[0]), Some(&99));
+
+        // and new items get a generation at least as new
+        let three = list.push_back(3);
+
+        assert_eq!(list.get(three), Some(&3));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 99, 3]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "cycle detected in IndexList")]
+    fn iter_detects_cycle() {
+        // the tail points back at the head, which can't happen through the
+        // public api
+        let list = IndexList {
+            contents: vec![
+                Entry::Occupied(OccupiedEntry {
+                    item: 5,
+                    next: Some(1),
+                    prev: None,
+                    generation: 0,
+                }),
+                Entry::Occupied(OccupiedEntry {
+                    item: 10,
+                    next: Some(0),
+                    prev: Some(0),
+                    generation: 0,
+                }),
+            ],
+            generation: 0,
+            next_free: None,
+            head: Some(0),
+            tail: Some(1),
+            len: 2,
+            tombstones: 0,
+            auto_trim: false,
+            removals_since_trim: 0,
+            free_strategy: FreeStrategy::Lifo,
+            free_tail: None,
+        };
+
+        for _ in list.iter() {}
+    }
+
+    #[test]
+    fn front_or_push() {
+        let mut list = IndexList::new();
+
+        assert_eq!(list.front_or_push(|&n| n == 5, 5), &5);
+        assert_eq!(list.len(), 1);
+
+        assert_eq!(list.front_or_push(|&n| n == 5, 5), &5);
+        assert_eq!(list.len(), 1);
+
+        assert_eq!(list.front_or_push(|&n| n == 10, 10), &10);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10, 5]);
+    }
+
+    #[test]
+    fn looks_valid_for() {
+        let mut long = IndexList::new();
+
+        long.push_back(1);
+        long.push_back(2);
+        let three = long.push_back(3);
+
+        let mut short = IndexList::new();
+
+        short.push_back(1);
+
+        assert!(three.looks_valid_for(&long));
+        assert!(!three.looks_valid_for(&short));
+
+        // only the slot is checked, not the generation
+        let (slot, _) = three.into_raw_parts();
+        let index = Index::from_raw_parts_checked(slot, 100, long.capacity()).unwrap();
+
+        assert!(index.looks_valid_for(&long));
+        assert_eq!(long.get(index), None);
+
+        assert!(Index::<i32>::from_raw_parts_checked(slot, 0, 1).is_none());
+    }
+
+    #[test]
+    fn push_back_mut() {
+        let mut list = IndexList::new();
+
+        list.push_back(1);
+
+        let five = {
+            let (index, item) = list.push_back_mut(5);
+            *item *= 2;
+            index
+        };
+
+        assert_eq!(list.get(five), Some(&10));
+        assert_eq!(list.tail_index(), Some(five));
+    }
+
+    #[test]
+    fn push_front_mut() {
+        let mut list = IndexList::new();
+
+        list.push_back(1);
+
+        *list.push_front_mut(5).1 = 0;
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn insert_before_mut() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        let one = {
+            let (index, item) = list.insert_before_mut(five, 0).unwrap();
+            *item = 1;
+            index
+        };
+
+        assert_eq!(list.head_index(), Some(one));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 5]);
+
+        list.remove(five);
+
+        assert!(list.insert_before_mut(five, 0).is_none());
+    }
+
+    #[test]
+    fn insert_after_mut() {
+        let mut list = IndexList::new();
+
+        let five = list.push_back(5);
+
+        *list.insert_after_mut(five, 0).unwrap().1 = 6;
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [5, 6]);
+
+        list.remove(five);
+
+        assert!(list.insert_after_mut(five, 0).is_none());
+    }
+
+    #[test]
+    fn collect_between() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        list.push_back(3);
+        let four = list.push_back(4);
+        let five = list.push_back(5);
+
+        assert_eq!(list.collect_between(two, four), Some(vec![&2, &3, &4]));
+        assert_eq!(list.collect_between(two, two), Some(vec![&2]));
+        assert_eq!(list.collect_between(four, two), None);
+
+        list.remove(five);
+
+        assert_eq!(list.collect_between(one, five), None);
+        assert_eq!(list.collect_between(five, one), None);
+    }
+
+    #[test]
+    fn join() {
+        let mut list = IndexList::new();
+
+        assert_eq!(list.join("-"), "");
+
+        list.push_back(String::from("a"));
+
+        assert_eq!(list.join("-"), "a");
+
+        list.push_back(String::from("b"));
+        list.push_back(String::from("c"));
+
+        assert_eq!(list.join("-"), "a-b-c");
+        assert_eq!(list.join(""), "abc");
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let mut list = IndexList::new();
+
+        assert_eq!(list.sum::<i32>(), 0);
+        assert_eq!(list.product::<i32>(), 1);
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        assert_eq!(list.sum::<i32>(), 6);
+        assert_eq!(list.product::<i32>(), 6);
+    }
+
+    #[test]
+    fn min_and_max_index() {
+        let mut list = IndexList::new();
+
+        assert_eq!(list.min_index(), None);
+        assert_eq!(list.max_index(), None);
+
+        list.push_back(3);
+        let first_one = list.push_back(1);
+        list.push_back(4);
+        list.push_back(1);
+        let five = list.push_back(5);
+
+        assert_eq!(list.min_index(), Some(first_one));
+        assert_eq!(list.max_index(), Some(five));
+        assert_eq!(list.get(list.min_index().unwrap()), Some(&1));
+        assert_eq!(list.get(list.max_index().unwrap()), Some(&5));
+    }
+
+    #[test]
+    fn drain_sorted() {
+        let mut list = IndexList::new();
+
+        list.push_back(3);
+        list.push_back(1);
+        list.push_back(2);
+
+        assert_eq!(list.drain_sorted().collect::<Vec<_>>(), [1, 2, 3]);
+
+        let empty: IndexList<i32> = IndexList::new();
+
+        assert_eq!(empty.drain_sorted().next(), None);
+    }
+
+    #[test]
+    fn push_back_tracked() {
+        let mut list = IndexList::new();
+
+        assert!(!list.push_back_tracked(5).1);
+        assert!(!list.push_back_tracked(10).1);
+
+        list.pop_front();
+
+        let (index, reused) = list.push_back_tracked(15);
+
+        assert!(reused);
+        assert_eq!(list.get(index), Some(&15));
+        assert!(!list.push_back_tracked(20).1);
+
+        // lazily removed slots count as reused too, once they're swept
+        let tail = list.tail_index().unwrap();
+        list.remove_lazy(tail);
+
+        assert!(list.push_back_tracked(25).1);
+    }
+
+    #[test]
+    fn from_slice() {
+        let list = IndexList::from_slice(&[1, 2, 3]);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+
+        let empty: IndexList<i32> = IndexList::from_slice(&[]);
+
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn split_by() {
+        let collect = |pieces: Vec<IndexList<i32>>| -> Vec<Vec<i32>> {
+            pieces
+                .into_iter()
+                .map(|piece| piece.into_iter().collect())
+                .collect()
+        };
+
+        let list = IndexList::from_slice(&[1, 2, 0, 3, 0, 4]);
+
+        assert_eq!(
+            collect(list.split_by(|&n| n == 0)),
+            vec![vec![1, 2], vec![3], vec![4]]
+        );
 
-        let removed = std::mem::replace(
-            &mut self.contents[head_index],
-            Entry::Free {
-                next_free: self.next_free,
-            },
+        let list = IndexList::from_slice(&[0, 1, 0, 0]);
+
+        assert_eq!(
+            collect(list.split_by(|&n| n == 0)),
+            vec![vec![], vec![1], vec![], vec![]]
         );
+    }
 
-        // update our free list to point to this new space
-        self.next_free = Some(head_index);
+    #[test]
+    fn take() {
+        let mut list = IndexList::new().with_auto_trim(true);
 
-        // when we remove a node, we need to increase the generation to invalidate
-        // older indexes that may be refering to this spot
-        self.generation += 1;
+        let one = list.push_back(1);
+        let two = list.push_back(2);
 
-        // now we need to fix up any next or previous nodes. we have two cases:
-        //
-        // * index is at the head and tail (only item in the list)
-        // * index is at the head
+        let taken = list.take();
 
-        // index is at the head and tail (only item in the list)
-        if Some(head_index) == self.tail {
-            self.head = None;
-            self.tail = None;
+        assert!(list.is_empty());
+        assert_eq!(list.head(), None);
+        assert!(list.auto_trim);
+        assert_eq!(taken.iter().cloned().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(taken.get(one), Some(&1));
+        assert_eq!(taken.get(two), Some(&2));
 
-        // index is at the head
-        } else {
-            let next = match &mut self.contents[next_index.unwrap()] {
-                Entry::Free { .. } => panic!("Corrupted list"),
-                Entry::Occupied(e) => e,
-            };
+        // the slot gets reused, but the old index doesn't see the new item
+        let three = list.push_back(3);
 
-            next.prev = None;
-            self.head = next_index;
-        }
+        assert_eq!(list.get(one), None);
+        assert_eq!(list.get(three), Some(&3));
+    }
 
-        match removed {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => Some(e.item),
+    #[test]
+    fn range_mut() {
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+
+        for item in list.range_mut(1, 3) {
+            *item *= 2;
         }
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 4, 6, 4, 5]);
+
+        assert_eq!(list.range_mut(3, 10).count(), 2);
+        assert_eq!(list.range_mut(3, 1).count(), 0);
+
+        // list order, not slot order, and tombstones are skipped
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+
+        let two = list.index_of(&2).unwrap();
+        let five = list.index_of(&5).unwrap();
+
+        list.move_before(five, two);
+        list.remove_lazy(two);
+
+        let items: Vec<_> = list.range_mut(1, 3).map(|item| *item).collect();
+
+        assert_eq!(items, [5, 3]);
+        assert_eq!(list.range_mut(0, 1).count(), 1);
     }
-}
 
-impl<T> IntoIterator for IndexList<T> {
-    type Item = T;
-    type IntoIter = IntoIter<T>;
+    #[test]
+    fn index_slot_and_generation() {
+        let mut list = IndexList::new();
 
-    fn into_iter(self) -> Self::IntoIter {
-        let next_index = self.head;
+        let five = list.push_back(5);
+        let ten = list.push_back(10);
 
-        IntoIter {
-            list: self,
-            next_index,
-        }
+        assert_eq!(five.slot(), 0);
+        assert_eq!(ten.slot(), 1);
+        assert_eq!(five.generation(), ten.generation());
+
+        list.remove(five);
+
+        let fifteen = list.push_back(15);
+
+        // same slot, newer generation, so the old index is stale
+        assert_eq!(fifteen.slot(), five.slot());
+        assert!(fifteen.generation() > five.generation());
+        assert_eq!(list.get(five), None);
     }
-}
 
-pub struct IntoIter<T> {
-    list: IndexList<T>,
-    next_index: Option<usize>,
-}
+    #[test]
+    fn list_generation() {
+        let mut list = IndexList::new();
 
-impl<T> Iterator for IntoIter<T> {
-    type Item = T;
+        let start = list.generation();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let next_index = self.next_index?;
-        let entry = std::mem::replace(
-            &mut self.list.contents[next_index],
-            Entry::Free { next_free: None },
-        );
+        let five = list.push_back(5);
+        list.push_back(10);
 
-        match entry {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => {
-                self.next_index = e.next;
+        assert_eq!(list.generation(), start);
 
-                Some(e.item)
-            }
-        }
+        list.remove(five);
+
+        let after_remove = list.generation();
+
+        assert!(after_remove > start);
+
+        let fifteen = list.push_back(15);
+
+        assert_eq!(list.generation(), after_remove);
+        assert_eq!(fifteen.generation(), list.generation());
     }
-}
 
-struct Iter<'a, T>
-where
-    T: 'a,
-{
-    list: &'a IndexList<T>,
-    next_index: Option<usize>,
-}
+    #[test]
+    fn batch_push_back() {
+        let mut list = IndexList::new();
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+        list.push_back(0);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // do we have a next thing?
-        let next_index = self.next_index?;
+        let items: Vec<_> = (1..=100).collect();
 
-        // what is it?
-        match &self.list.contents[next_index] {
-            Entry::Free { .. } => panic!("Corrupted list"),
-            Entry::Occupied(e) => {
-                // set up our next iteration
-                self.next_index = e.next;
+        // reserving the same amount ourselves tells us what the batch's
+        // single reservation should end up with
+        let mut expected: Vec<i32> = Vec::with_capacity(list.capacity());
+        expected.push(0);
+        expected.reserve(items.len());
 
-                Some(&e.item)
-            }
-        }
+        let indexes = list.batch_push_back(items);
+
+        assert_eq!(list.capacity(), expected.capacity());
+        assert_eq!(indexes.len(), 100);
+        assert_eq!(list.len(), 101);
+        assert_eq!(list.get(indexes[0]), Some(&1));
+        assert_eq!(list.get(indexes[99]), Some(&100));
     }
-}
 
-impl<T> std::ops::Index<Index<T>> for IndexList<T>
-where
-    T: PartialEq,
-    T: std::fmt::Debug,
-{
-    type Output = T;
+    #[test]
+    fn contains_all_and_any() {
+        let list = IndexList::from_slice(&[1, 2, 3]);
 
-    fn index(&self, index: Index<T>) -> &Self::Output {
-        self.get(index).unwrap()
+        assert!(list.contains_all(&[1, 3]));
+        assert!(list.contains_all(&[]));
+        assert!(!list.contains_all(&[1, 4]));
+
+        assert!(list.contains_any(&[9, 3]));
+        assert!(!list.contains_any(&[9, 8]));
+        assert!(!list.contains_any(&[]));
     }
-}
 
-impl<T> std::ops::IndexMut<Index<T>> for IndexList<T>
-where
-    T: PartialEq,
-    T: std::fmt::Debug,
-{
-    fn index_mut(&mut self, index: Index<T>) -> &mut Self::Output {
-        self.get_mut(index).unwrap()
+    #[test]
+    fn dedup_all() {
+        let mut list = IndexList::from_slice(&[1, 2, 1, 3, 2]);
+
+        let first_one = list.head_index().unwrap();
+
+        list.dedup_all();
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(list.head_index(), Some(first_one));
+        assert_eq!(list.len(), 3);
+
+        list.dedup_all();
+
+        assert_eq!(list.len(), 3);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn swap_ends() {
+        let mut list = IndexList::new();
+
+        assert!(!list.swap_ends());
+
+        let one = list.push_back(1);
+
+        assert!(!list.swap_ends());
+
+        list.push_back(2);
+        let three = list.push_back(3);
+
+        assert!(list.swap_ends());
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [3, 2, 1]);
+
+        // the items moved, the nodes didn't
+        assert_eq!(list.get(one), Some(&3));
+        assert_eq!(list.get(three), Some(&1));
+        assert_eq!(list.head_index(), Some(one));
+    }
 
     #[test]
-    fn create_index() {
-        let index: Index<i32> = Index::new(1, 2);
+    fn retain_first_n() {
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
 
-        assert_eq!(index.index, 1);
-        assert_eq!(index.generation, 2);
+        list.retain_first_n(2, |n| n % 2 == 1);
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+        list.retain_first_n(0, |n| n % 2 == 0);
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 3]);
+
+        list.retain_first_n(10, |_| true);
+
+        assert_eq!(list.len(), 2);
     }
 
     #[test]
-    fn create_list() {
-        let _list: IndexList<i32> = IndexList::new();
+    fn index_snapshot() {
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
+
+        let snapshot = list.index_snapshot();
+
+        assert_eq!(snapshot.len(), 5);
+        assert_eq!(snapshot[0], list.head_index().unwrap());
+
+        for (i, &index) in snapshot.iter().enumerate() {
+            if i % 2 == 1 {
+                list.remove(index);
+            }
+        }
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 3, 5]);
+        assert_eq!(list.get(snapshot[2]), Some(&3));
+        assert_eq!(list.get(snapshot[3]), None);
     }
 
     #[test]
-    fn insert() {
+    fn move_before() {
         let mut list = IndexList::new();
 
-        list.push_back(5);
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let three = list.push_back(3);
+
+        assert!(list.move_before(three, one));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [3, 1, 2]);
+        assert_eq!(list.head_index(), Some(three));
+        assert_eq!(list.tail_index(), Some(two));
+        assert_eq!(list.get(one), Some(&1));
+        assert_eq!(list.get(two), Some(&2));
+        assert_eq!(list.get(three), Some(&3));
+
+        // and walking backwards agrees
+        assert_eq!(list.prev_index(two), Some(one));
+        assert_eq!(list.prev_index(one), Some(three));
+        assert_eq!(list.prev_index(three), None);
+
+        assert!(!list.move_before(one, one));
+
+        list.remove(two);
+
+        assert!(!list.move_before(two, one));
+        assert!(!list.move_before(one, two));
+    }
+
+    #[test]
+    fn move_after() {
+        let mut list = IndexList::new();
+
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let three = list.push_back(3);
+
+        assert!(list.move_after(one, two));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 1, 3]);
+        assert_eq!(list.head_index(), Some(two));
+
+        assert!(list.move_after(two, three));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 3, 2]);
+        assert_eq!(list.tail_index(), Some(two));
+        assert_eq!(list.next_index(three), Some(two));
+
+        assert!(!list.move_after(three, three));
+    }
+
+    #[test]
+    fn group_by() {
+        let collect = |groups: Vec<IndexList<i32>>| -> Vec<Vec<i32>> {
+            groups
+                .into_iter()
+                .map(|group| group.into_iter().collect())
+                .collect()
+        };
+
+        let list = IndexList::from_slice(&[1, 1, 2, 3, 3]);
 
         assert_eq!(
-            list.contents[0],
-            Entry::Occupied(OccupiedEntry {
-                item: 5,
-                next: None,
-                prev: None,
-                generation: 0,
-            })
+            collect(list.group_by(|&n| n)),
+            vec![vec![1, 1], vec![2], vec![3, 3]]
+        );
+
+        let list = IndexList::from_slice(&[1, 3, 2, 4, 5]);
+
+        assert_eq!(
+            collect(list.group_by(|&n| n % 2)),
+            vec![vec![1, 3], vec![2, 4], vec![5]]
         );
+
+        let empty: IndexList<i32> = IndexList::new();
+
+        assert!(empty.group_by(|&n| n).is_empty());
     }
 
     #[test]
-    fn contains() {
+    fn replace_all() {
         let mut list = IndexList::new();
 
-        list.push_back(5);
+        let one = list.push_back(1);
+        list.push_back(2);
+        list.push_back(1);
+        list.push_back(3);
 
-        assert!(list.contains(&5));
+        assert_eq!(list.replace_all(&1, 9), 2);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [9, 2, 9, 3]);
+        assert_eq!(list.get(one), Some(&9));
+
+        assert_eq!(list.replace_all(&1, 9), 0);
     }
 
     #[test]
-    fn get() {
+    fn truncate_front() {
         let mut list = IndexList::new();
 
-        let five = list.push_back(5);
+        let one = list.push_back(1);
+        list.push_back(2);
+        let three = list.push_back(3);
+        list.push_back(4);
 
-        let entry = list.get(five);
+        list.truncate_front(2);
 
-        assert!(entry.is_some());
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [3, 4]);
+        assert_eq!(list.head_index(), Some(three));
+        assert_eq!(list.get(one), None);
+        assert_eq!(list.free_slots(), 2);
 
-        assert_eq!(entry.unwrap(), &5);
+        list.truncate_front(10);
+
+        assert!(list.is_empty());
     }
 
     #[test]
-    fn get_mut() {
+    fn run_lengths() {
+        let list = IndexList::from_slice(&[1, 1, 2, 3, 3, 3]);
+
+        assert_eq!(list.run_lengths(), vec![(2, &1), (1, &2), (3, &3)]);
+
+        let list = IndexList::from_slice(&[1, 2, 1]);
+
+        assert_eq!(list.run_lengths(), vec![(1, &1), (1, &2), (1, &1)]);
+
+        let empty: IndexList<i32> = IndexList::new();
+
+        assert!(empty.run_lengths().is_empty());
+    }
+
+    #[test]
+    fn peek() {
         let mut list = IndexList::new();
 
-        let five = list.push_back(5);
+        assert_eq!(list.peek_front(), None);
+        assert_eq!(list.peek_back(), None);
+        assert_eq!(list.peek_nth_back(0), None);
 
-        let entry = list.get_mut(five);
+        list.push_back(1);
+        let two = list.push_back(2);
+        list.push_back(3);
 
-        assert!(entry.is_some());
+        assert_eq!(list.peek_front(), Some(&1));
+        assert_eq!(list.peek_back(), Some(&3));
+        assert_eq!(list.peek_nth_front(1), Some(&2));
+        assert_eq!(list.peek_nth_back(1), Some(&2));
+        assert_eq!(list.peek_nth_back(2), Some(&1));
+        assert_eq!(list.peek_nth_back(3), None);
 
-        assert_eq!(entry.unwrap(), &mut 5);
+        list.remove(two);
+
+        assert_eq!(list.peek_nth_back(1), Some(&1));
+        assert_eq!(list.len(), 2);
     }
 
     #[test]
-    fn next_index() {
+    fn retain_mask() {
+        let mut list = IndexList::from_slice(&[10, 20, 30]);
+
+        list.retain_mask(&IndexList::from_slice(&[true, false, true]));
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10, 30]);
+
+        // a short mask leaves the rest alone
+        let mut list = IndexList::from_slice(&[10, 20, 30]);
+
+        list.retain_mask(&IndexList::from_slice(&[false]));
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [20, 30]);
+
+        // and extra mask entries are ignored
+        list.retain_mask(&IndexList::from_slice(&[true, true, false, false]));
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [20, 30]);
+    }
+
+    #[test]
+    fn index_display() {
+        let index: Index<i32> = Index::new(3, 1);
+
+        assert_eq!(format!("{}", index), "#3@g1");
+        assert_eq!(index.to_string(), "#3@g1");
+    }
+
+    #[test]
+    fn from_positioned() {
+        let list = IndexList::from_positioned(vec![(2, 'c'), (0, 'a'), (1, 'b')]);
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), ['a', 'b', 'c']);
+
+        // gaps are skipped, and ties keep their order
+        let list = IndexList::from_positioned(vec![(10, 'c'), (5, 'a'), (5, 'b')]);
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), ['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn into_iter_indexed() {
         let mut list = IndexList::new();
 
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        list.remove(one);
+        list.push_back(3);
+        list.push_front(0);
+
+        let before: Vec<_> = list.index_snapshot();
+        let three = list.index_of(&3).unwrap();
+
+        let pairs: Vec<_> = list.into_iter_indexed().collect();
+
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[1], (two, 2));
+        assert_eq!(pairs[2], (three, 3));
+        assert_eq!(
+            pairs.iter().map(|&(index, _)| index).collect::<Vec<_>>(),
+            before
+        );
+    }
+
+    #[test]
+    fn free_strategy() {
+        let reuse_order = |strategy| {
+            let mut list = IndexList::new().with_free_strategy(strategy);
+
+            let indexes: Vec<_> = (0..4).map(|i| list.push_back(i)).collect();
+
+            list.remove(indexes[0]);
+            list.remove(indexes[2]);
+            list.remove(indexes[1]);
+
+            (0..3).map(|i| list.push_back(i).slot()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(reuse_order(FreeStrategy::Lifo), [1, 2, 0]);
+        assert_eq!(reuse_order(FreeStrategy::Fifo), [0, 2, 1]);
+    }
+
+    #[test]
+    fn free_strategy_fifo_keeps_tail() {
+        let mut list = IndexList::new().with_free_strategy(FreeStrategy::Fifo);
+
         let five = list.push_back(5);
-        let _ten = list.push_back(10);
+        let ten = list.push_back(10);
+        list.push_back(15);
 
-        let ten_index = list.next_index(five).unwrap();
+        list.remove(five);
 
-        let ten_value = list.get(ten_index);
+        // empties the free list, so the tail has to be forgotten
+        let twenty = list.push_back(20);
+
+        assert_eq!(twenty.slot(), five.slot());
+        assert_eq!(list.free_tail, None);
+
+        list.remove(ten);
+        list.remove(twenty);
+
+        assert_eq!(list.free_tail, Some(twenty.slot()));
+        assert_eq!(list.push_back(25).slot(), ten.slot());
+        assert_eq!(list.push_back(30).slot(), twenty.slot());
+        assert_eq!(list.push_back(35).slot(), 3);
+
+        list.remove_lazy(list.head_index().unwrap());
+        list.sweep();
 
-        assert_eq!(ten_value.unwrap(), &10);
-        assert_eq!(None, list.next_index(ten_index));
+        assert_eq!(list.free_tail, Some(2));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [25, 30, 35]);
     }
 
     #[test]
-    fn prev_index() {
+    fn distance() {
         let mut list = IndexList::new();
 
-        let _five = list.push_back(5);
-        let ten = list.push_back(10);
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        list.push_back(3);
+        let four = list.push_back(4);
 
-        let five_index = list.prev_index(ten).unwrap();
+        assert_eq!(list.distance(two, four), Some(2));
+        assert_eq!(list.distance(one, four), Some(3));
+        assert_eq!(list.distance(two, two), Some(0));
+        assert_eq!(list.distance(four, one), None);
 
-        let five_value = list.get(five_index);
+        list.remove(two);
 
-        assert_eq!(five_value.unwrap(), &5);
-        assert_eq!(None, list.prev_index(five_index));
+        assert_eq!(list.distance(one, four), Some(2));
+        assert_eq!(list.distance(two, four), None);
+        assert_eq!(list.distance(one, two), None);
     }
 
     #[test]
-    fn index() {
-        let mut list = IndexList::new();
+    fn remove_all_matching() {
+        let mut list = IndexList::from_slice(&[1, 2, 1, 3, 1]);
 
-        let five = list.push_back(5);
+        assert_eq!(list.remove_all_matching(&1), 3);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(list.len(), 2);
 
-        let entry = list[five];
+        assert_eq!(list.remove_all_matching(&1), 0);
 
-        assert_eq!(entry, 5);
+        let mut list = IndexList::from_slice(&[4, 4, 4]).with_auto_trim(true);
+
+        assert_eq!(list.remove_all_matching(&4), 3);
+        assert!(list.is_empty());
     }
 
     #[test]
-    fn index_mut() {
-        let mut list = IndexList::new();
+    fn with_capacity_and_generation() {
+        let mut old = IndexList::new();
 
-        let five = list.push_back(5);
+        let five = old.push_back(5);
+        old.remove(five);
+        let ten = old.push_back(10);
 
-        let mut entry = list[five];
+        let (slot, generation) = ten.into_raw_parts();
 
-        entry += 1;
+        // rebuild it from scratch, and the saved index works again
+        let mut list = IndexList::with_capacity_and_generation(4, generation);
 
-        let six = list.push_back(entry);
+        assert!(list.capacity() >= 4);
+        assert_eq!(list.generation(), generation);
 
-        let new_entry = list[six];
+        let rebuilt = list.push_back(10);
 
-        assert_eq!(new_entry, 6);
+        assert_eq!(rebuilt.into_raw_parts(), (slot, generation));
+        assert_eq!(list.get(ten), Some(&10));
     }
 
     #[test]
-    fn insert_thrice() {
-        let mut list = IndexList::new();
+    fn drain_range() {
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
 
-        list.push_back(5);
-        list.push_back(10);
-        list.push_back(15);
+        assert_eq!(list.drain_range(1, 3), vec![2, 3]);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [1, 4, 5]);
 
-        assert_eq!(
-            list.contents[0],
-            Entry::Occupied(OccupiedEntry {
-                item: 5,
-                next: Some(1),
-                prev: None,
-                generation: 0,
-            })
-        );
+        assert_eq!(list.drain_range(2, 1), vec![]);
+        assert_eq!(list.drain_range(5, 10), vec![]);
+        assert_eq!(list.len(), 3);
 
-        assert_eq!(
-            list.contents[1],
-            Entry::Occupied(OccupiedEntry {
-                item: 10,
-                next: Some(2),
-                prev: Some(0),
-                generation: 0,
-            })
-        );
+        assert_eq!(list.drain_range(1, 10), vec![4, 5]);
+        assert_eq!(list.tail_index(), list.head_index());
 
-        assert_eq!(
-            list.contents[2],
-            Entry::Occupied(OccupiedEntry {
-                item: 15,
-                next: None,
-                prev: Some(1),
-                generation: 0,
-            })
-        );
+        assert_eq!(list.drain_range(0, 1), vec![1]);
+        assert!(list.is_empty());
     }
 
     #[test]
-    fn remove_middle() {
+    fn swap_remove_tracked() {
         let mut list = IndexList::new();
 
+        let one = list.push_back(1);
+        let two = list.push_back(2);
+        let three = list.push_back(3);
+        let zero = list.push_front(0);
+
+        // zero is in the last slot, so it moves into one's slot
+        let (item, moved) = list.swap_remove_tracked(one).unwrap();
+        let moved = moved.unwrap();
+
+        assert_eq!(item, 1);
+        assert_eq!(moved.slot(), one.slot());
+        assert_eq!(list.get(moved), Some(&0));
+        assert_eq!(list.get(zero), None);
+        assert_eq!(list.get(one), None);
+        assert_eq!(list.head_index(), Some(moved));
+        assert_eq!(list.prev_index(two), Some(moved));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [0, 2, 3]);
+        assert_eq!(list.free_slots(), 0);
+
+        // removing the last slot doesn't move anything
+        assert_eq!(list.swap_remove_tracked(three), Some((3, None)));
+        assert_eq!(list.tail_index(), Some(two));
+        assert_eq!(list.free_slots(), 0);
+
+        assert_eq!(list.swap_remove_tracked(three), None);
+
+        // a free last slot falls back to a normal removal
+        let four = list.push_back(4);
         list.push_back(5);
-        let ten = list.push_back(10);
-        list.push_back(15);
-
-        let removed = list.remove(ten).unwrap();
-
-        assert_eq!(removed, 10);
+        list.pop_back();
 
-        assert_eq!(
-            list,
-            IndexList {
-                contents: vec![
-                    Entry::Occupied(OccupiedEntry {
-                        item: 5,
-                        next: Some(2),
-                        prev: None,
-                        generation: 0,
-                    }),
-                    Entry::Free { next_free: None },
-                    Entry::Occupied(OccupiedEntry {
-                        item: 15,
-                        next: None,
-                        prev: Some(0),
-                        generation: 0,
-                    }),
-                ],
-                generation: 1,
-                next_free: Some(1),
-                head: Some(0),
-                tail: Some(2),
-            }
-        );
+        assert_eq!(list.swap_remove_tracked(moved), Some((0, None)));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 4]);
+        assert_eq!(list.get(four), Some(&4));
+        assert_eq!(list.free_slots(), 2);
     }
 
     #[test]
-    fn remove_head() {
-        let mut list = IndexList::new();
-
-        let five = list.push_back(5);
-        list.push_back(10);
-        list.push_back(15);
-
-        let removed = list.remove(five).unwrap();
+    fn reachable_len() {
+        let mut list = IndexList::from_slice(&[1, 2, 3]);
 
-        assert_eq!(removed, 5);
+        assert_eq!(list.reachable_len(), 3);
 
-        assert_eq!(
-            list,
-            IndexList {
-                contents: vec![
-                    Entry::Free { next_free: None },
-                    Entry::Occupied(OccupiedEntry {
-                        item: 10,
-                        next: Some(2),
-                        prev: None,
-                        generation: 0,
-                    }),
-                    Entry::Occupied(OccupiedEntry {
-                        item: 15,
-                        next: None,
-                        prev: Some(1),
-                        generation: 0,
-                    }),
-                ],
-                generation: 1,
-                next_free: Some(0),
-                head: Some(1),
-                tail: Some(2),
-            }
-        );
-    }
+        let two = list.index_of(&2).unwrap();
+        list.remove_lazy(two);
 
-    #[test]
-    fn remove_tail() {
-        let mut list = IndexList::new();
+        assert_eq!(list.reachable_len(), list.len());
 
-        list.push_back(5);
-        list.push_back(10);
-        let fifteen = list.push_back(15);
+        // cut the list off after the head
+        list.sweep();
+        let head = list.head.unwrap();
+        list.occupied_mut(head).next = None;
 
-        let removed = list.remove(fifteen).unwrap();
+        assert_eq!(list.reachable_len(), 1);
+        assert_ne!(list.reachable_len(), list.len());
 
-        assert_eq!(removed, 15);
+        // and make it go around in a circle
+        list.occupied_mut(head).next = Some(head);
 
-        assert_eq!(
-            list,
-            IndexList {
-                contents: vec![
-                    Entry::Occupied(OccupiedEntry {
-                        item: 5,
-                        next: Some(1),
-                        prev: None,
-                        generation: 0,
-                    }),
-                    Entry::Occupied(OccupiedEntry {
-                        item: 10,
-                        next: None,
-                        prev: Some(0),
-                        generation: 0,
-                    }),
-                    Entry::Free { next_free: None },
-                ],
-                generation: 1,
-                next_free: Some(2),
-                head: Some(0),
-                tail: Some(1),
-            }
-        );
+        assert!(list.reachable_len() > list.len());
     }
 
     #[test]
-    fn remove_only() {
+    fn insert_all_before() {
         let mut list = IndexList::new();
 
-        let five = list.push_back(5);
+        let one = list.push_back(1);
+        let two = list.push_back(2);
 
-        let removed = list.remove(five).unwrap();
+        let indexes = list.insert_all_before(one, vec![7, 8]).unwrap();
 
-        assert_eq!(removed, 5);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [7, 8, 1, 2]);
+        assert_eq!(list.get(indexes[0]), Some(&7));
+        assert_eq!(list.get(indexes[1]), Some(&8));
+        assert_eq!(list.head_index(), Some(indexes[0]));
 
-        assert_eq!(
-            list,
-            IndexList {
-                contents: vec![Entry::Free { next_free: None },],
-                generation: 1,
-                next_free: Some(0),
-                head: None,
-                tail: None,
-            }
-        );
+        list.insert_all_before(two, 3..5).unwrap();
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [7, 8, 1, 3, 4, 2]);
+
+        list.remove(one);
+
+        assert!(list.insert_all_before(one, vec![0]).is_none());
+        assert_eq!(list.len(), 5);
     }
 
     #[test]
-    fn remove_returns_none_when_not_there() {
+    fn head_tail_mut() {
         let mut list = IndexList::new();
 
-        let five_index = list.push_back(5);
+        assert!(list.head_tail_mut().is_none());
 
-        let five_entry = list.remove(five_index).unwrap();
+        list.push_back(1);
 
-        assert_eq!(list.contents[0], Entry::Free { next_free: None });
+        assert!(list.head_tail_mut().is_none());
 
-        assert_eq!(five_entry, 5);
+        list.push_back(2);
+        list.push_back(3);
 
-        assert!(list.remove(five_index).is_none());
+        {
+            let (head, tail) = list.head_tail_mut().unwrap();
+            *head = 10;
+            *tail = 30;
+        }
+
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [10, 2, 30]);
     }
 
     #[test]
-    fn into_iter() {
+    fn try_for_each_index() {
         let mut list = IndexList::new();
 
-        list.push_back(5);
-        let ten = list.push_back(10);
-        list.push_back(15);
+        list.push_back(2);
+        list.push_back(4);
+        let five = list.push_back(5);
+        list.push_back(7);
 
-        list.remove(ten);
+        let mut seen = 0;
 
-        let mut iter = list.into_iter();
+        let result = list.try_for_each_index(|_, &n| {
+            seen += 1;
 
-        assert_eq!(iter.next().unwrap(), 5);
-        assert_eq!(iter.next().unwrap(), 15);
+            if n % 2 == 0 {
+                Ok(())
+            } else {
+                Err("odd")
+            }
+        });
 
-        assert!(iter.next().is_none());
+        assert_eq!(result, Err((five, "odd")));
+        assert_eq!(seen, 3);
+
+        assert_eq!(list.try_for_each_index(|_, _| Ok::<(), ()>(())), Ok(()));
     }
 
     #[test]
-    fn iter() {
+    fn index_u128() {
         let mut list = IndexList::new();
 
-        list.push_back(5);
+        let five = list.push_back(5);
+        list.remove(five);
+        list.push_back(6);
         let ten = list.push_back(10);
-        list.push_back(15);
 
-        list.remove(ten);
+        let packed = ten.as_u128();
 
-        let mut iter = list.iter();
+        assert_eq!(packed, (1 << 64) | 1);
+        assert_eq!(Index::from_u128(packed), ten);
+        assert_eq!(list.get(Index::from_u128(packed)), Some(&10));
 
-        assert_eq!(iter.next().unwrap(), &5);
-        assert_eq!(iter.next().unwrap(), &15);
+        let big: Index<i32> = Index::new(usize::MAX, usize::MAX - 1);
 
-        assert!(iter.next().is_none());
+        assert_eq!(Index::from_u128(big.as_u128()), big);
     }
 
     #[test]
-    fn reallocation() {
-        let mut list = IndexList::new();
+    fn for_each_mut_removing() {
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4]);
 
-        list.push_back(5);
-        let ten = list.push_back(10);
-        list.push_back(15);
+        let two = list.index_of(&2).unwrap();
+        let four = list.index_of(&4).unwrap();
 
-        let ten = list.remove(ten).unwrap();
+        list.for_each_mut_removing(|x| {
+            *x *= 2;
+            *x > 6
+        });
 
-        assert_eq!(ten, 10);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [2, 4, 6]);
+        assert_eq!(list.get(two), Some(&4));
+        assert_eq!(list.get(four), None);
+        assert_eq!(list.len(), 3);
+    }
 
-        list.push_back(20);
+    #[test]
+    fn eq_slice() {
+        let mut list = IndexList::from_slice(&[1, 2, 3]);
 
-        assert_eq!(
-            list.contents[0],
-            Entry::Occupied(OccupiedEntry {
-                item: 5,
-                next: Some(2),
-                prev: None,
-                generation: 0,
-            })
-        );
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list, vec![1, 2, 3]);
+        assert_eq!(list, &[1, 2, 3][..]);
+        assert_eq!(list, [1, 2, 3][..]);
 
-        assert_eq!(
-            list.contents[1],
-            Entry::Occupied(OccupiedEntry {
-                item: 20,
-                next: None,
-                prev: Some(2),
-                generation: 1,
-            })
-        );
+        assert_ne!(list, [1, 2]);
+        assert_ne!(list, [1, 2, 3, 4]);
+        assert_ne!(list, [1, 2, 4]);
 
-        assert_eq!(
-            list.contents[2],
-            Entry::Occupied(OccupiedEntry {
-                item: 15,
-                next: Some(1),
-                prev: Some(0),
-                generation: 0,
-            })
-        );
+        // removed items don't count
+        let two = list.index_of(&2).unwrap();
+        list.remove(two);
+
+        assert_eq!(list, [1, 3]);
+        assert_ne!(IndexList::<i32>::new(), [1]);
+        assert_eq!(IndexList::<i32>::new(), []);
     }
 
     #[test]
-    fn generations() {
+    fn get_checked() {
         let mut list = IndexList::new();
 
         let five = list.push_back(5);
         let ten = list.push_back(10);
-        list.push_back(15);
 
-        list.remove(ten);
+        assert_eq!(list.get_checked(five), Ok(&5));
+        assert_eq!(
+            list.get_checked(Index::new(7, 0)),
+            Err(IndexError::OutOfBounds)
+        );
 
-        let twenty = list.push_back(20);
+        list.remove(five);
 
-        // since we reallocate, that twenty should have gone where the ten was.
-        // this means that ten should now be invalid.
-        assert!(list.get(ten).is_none());
+        assert_eq!(list.get_checked(five), Err(IndexError::Freed));
 
-        // however, five should be fine!
-        assert!(list.get(five).is_some());
+        // the slot gets reused, so now the index is just old
+        list.push_back(15);
+
+        assert_eq!(list.get_checked(five), Err(IndexError::Stale));
+        assert_eq!(list.get_checked(ten), Ok(&10));
 
-        // as should twenty!
-        assert!(list.get(twenty).is_some());
+        list.remove_lazy(ten);
+
+        assert_eq!(list.get_checked(ten), Err(IndexError::Freed));
     }
 
     #[test]
-    fn head() {
+    fn rebuild_free_list() {
         let mut list = IndexList::new();
 
-        assert!(list.head().is_none());
+        let indexes: Vec<_> = (0..5).map(|i| list.push_back(i)).collect();
+        list.remove(indexes[1]);
+        list.remove(indexes[3]);
 
-        let five = list.push_back(5);
+        // lose track of the free slots
+        list.next_free = None;
+        list.free_tail = None;
 
-        assert_eq!(list.head().unwrap(), &5);
+        list.rebuild_free_list();
 
-        list.push_back(10);
+        let a = list.push_back(10);
+        let b = list.push_back(11);
 
-        list.remove(five);
+        let mut slots = vec![a.index, b.index];
+        slots.sort();
 
-        assert_eq!(list.head().unwrap(), &10);
+        assert_eq!(slots, [1, 3]);
+        assert_eq!(list.contents.len(), 5);
+        assert_eq!(list.next_free, None);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), [0, 2, 4, 10, 11]);
 
-        assert_eq!(list.contents[0], Entry::Free { next_free: None });
+        // fifo lists get their chain back in slot order
+        let mut list = IndexList::new().with_free_strategy(FreeStrategy::Fifo);
 
-        assert_eq!(list.head, Some(1));
+        let indexes: Vec<_> = (0..3).map(|i| list.push_back(i)).collect();
+        list.remove(indexes[2]);
+        list.remove(indexes[0]);
+        list.next_free = None;
 
-        assert_eq!(
-            list.contents[1],
-            Entry::Occupied(OccupiedEntry {
-                item: 10,
-                next: None,
-                prev: None,
-                generation: 0,
-            })
-        );
+        list.rebuild_free_list();
+
+        assert_eq!(list.push_back(10).index, 0);
+        assert_eq!(list.push_back(11).index, 2);
     }
 
     #[test]
-    fn head_mut() {
+    fn fragmentation() {
         let mut list = IndexList::new();
 
-        assert!(list.head_mut().is_none());
+        assert_eq!(list.fragmentation(), 0.0);
 
-        let five = list.push_back(5);
+        let indexes: Vec<_> = (0..10).map(|i| list.push_back(i)).collect();
 
-        assert_eq!(list.head_mut().unwrap(), &mut 5);
+        assert_eq!(list.fragmentation(), 0.0);
 
-        list.push_back(10);
+        for &index in indexes.iter().step_by(2) {
+            list.remove(index);
+        }
 
-        list.remove(five);
+        assert!((list.fragmentation() - 0.5).abs() < 1e-9);
+        assert_eq!(list.free_slots(), 5);
+    }
 
-        assert_eq!(list.head_mut().unwrap(), &mut 10);
+    #[test]
+    fn swap_contents() {
+        let mut a = IndexList::new();
+        let mut b = IndexList::new();
 
-        assert_eq!(list.contents[0], Entry::Free { next_free: None });
+        // both lists hand out an index for slot 0 at generation 0
+        let a_one = a.push_back(1);
+        let b_ten = b.push_back(10);
+        b.push_back(20);
 
-        assert_eq!(list.head, Some(1));
+        assert_eq!(a_one, b_ten);
 
-        assert_eq!(
-            list.contents[1],
-            Entry::Occupied(OccupiedEntry {
-                item: 10,
-                next: None,
-                prev: None,
-                generation: 0,
-            })
-        );
+        a.swap_contents(&mut b);
+
+        assert_eq!(a.iter().cloned().collect::<Vec<_>>(), [10, 20]);
+        assert_eq!(b.iter().cloned().collect::<Vec<_>>(), [1]);
+
+        // with a plain swap, `a_one` would now find 10 in `a`
+        assert_eq!(a.get(a_one), None);
+        assert_eq!(b.get(a_one), None);
+        assert_eq!(a.get(b_ten), None);
+        assert_eq!(b.get(b_ten), None);
+
+        assert_eq!(a.generation(), 1);
+        assert_eq!(b.generation(), 1);
+
+        // new indexes work as usual, and the free chains came along too
+        let one = b.head_index().unwrap();
+        b.remove(one);
+        let two = b.push_back(2);
+
+        assert_eq!(two.index, 0);
+        assert_eq!(b.get(two), Some(&2));
+        assert_eq!(b.get(one), None);
+        assert_eq!(a.len(), 2);
+        assert_eq!(b.len(), 1);
     }
 
     #[test]
-    fn head_index() {
-        let mut list = IndexList::new();
-
-        assert!(list.head_index().is_none());
+    fn into_sorted_vec() {
+        let mut list = IndexList::from_slice(&[3, 1, 2, 1]);
 
-        let five = list.push_back(5);
+        let three = list.head_index().unwrap();
+        list.remove(three);
 
-        assert_eq!(list.head_index().unwrap(), five);
+        assert_eq!(list.into_sorted_vec(), [1, 1, 2]);
+        assert_eq!(IndexList::<i32>::new().into_sorted_vec(), []);
     }
 
     #[test]
-    fn tail_index() {
-        let mut list = IndexList::new();
+    fn flat_map() {
+        let list = IndexList::from_slice(&[1, 2]);
 
-        assert!(list.tail_index().is_none());
+        let expanded = list.flat_map(|x| vec![x, x * 10]);
 
-        let _five = list.push_back(5);
-        let ten = list.push_back(10);
+        assert_eq!(expanded, [1, 10, 2, 20]);
 
-        assert_eq!(list.tail_index().unwrap(), ten);
+        // items can also expand to nothing, or change type
+        let list = IndexList::from_slice(&[0, 1, 2]);
+
+        let words = list.flat_map(|x| std::iter::repeat_n("a", x));
+
+        assert_eq!(words, ["a", "a", "a"]);
     }
 
     #[test]
-    fn push_front() {
-        let mut list = IndexList::new();
+    fn chunk_indices() {
+        let list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
 
-        list.push_front(5);
-        list.push_front(10);
-        list.push_front(15);
+        let chunks = list.chunk_indices(2);
 
         assert_eq!(
-            list.contents[0],
-            Entry::Occupied(OccupiedEntry {
-                item: 5,
-                next: None,
-                prev: Some(1),
-                generation: 0,
-            })
+            chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            [2, 2, 1]
         );
 
-        assert_eq!(
-            list.contents[1],
-            Entry::Occupied(OccupiedEntry {
-                item: 10,
-                next: Some(0),
-                prev: Some(2),
-                generation: 0,
-            })
-        );
+        let items: Vec<Vec<i32>> = chunks
+            .iter()
+            .map(|c| c.iter().map(|&i| list[i]).collect())
+            .collect();
 
-        assert_eq!(
-            list.contents[2],
-            Entry::Occupied(OccupiedEntry {
-                item: 15,
-                next: Some(1),
-                prev: None,
-                generation: 0,
-            })
-        );
+        assert_eq!(items, [vec![1, 2], vec![3, 4], vec![5]]);
+        assert!(IndexList::<i32>::new().chunk_indices(3).is_empty());
     }
 
     #[test]
-    fn index_of() {
-        let mut list = IndexList::new();
-
-        list.push_back(5);
-        list.push_back(10);
-        list.push_back(15);
-
-        assert_eq!(list.index_of(&10).unwrap(), Index::new(1, 0));
+    #[should_panic]
+    fn chunk_indices_zero() {
+        let list = IndexList::from_slice(&[1]);
 
-        assert!(list.index_of(&20).is_none());
+        let _ = list.chunk_indices(0);
     }
 
     #[test]
-    fn index_of_get_correct_generation() {
+    fn node_id() {
         let mut list = IndexList::new();
 
-        list.push_back(5);
-        let ten = list.push_back(10);
-        list.remove(ten);
-        list.push_back(15);
+        let one = list.push_back(1);
+        list.push_back(2);
+        let three = list.push_back(3);
 
-        assert_eq!(
-            list.index_of(&5).unwrap(),
-            Index {
-                index: 0,
-                generation: 0,
-                _marker: PhantomData
-            }
-        );
+        let id = list.id(three);
+
+        assert!(list.move_before(three, one));
+        assert_eq!(list.head_index(), Some(three));
+        assert_eq!(list.resolve(id), Some(three));
+        assert_eq!(list.resolve(id).map(|i| list[i]), Some(3));
+
+        // compacting gives every node a new index, so old ids go stale
+        list.remove(one);
+        list.compact();
+
+        assert_eq!(list.resolve(id), None);
+
+        let head = list.head_index().unwrap();
+        let id = list.id(head);
+
+        list.remove(head);
+        list.push_front(3);
+
+        assert_eq!(list.resolve(id), None);
     }
 
     #[test]
-    fn index_of_get_first_occurrence() {
+    fn enumerate_indexed() {
         let mut list = IndexList::new();
 
-        list.push_back(3);
-        let six = list.push_back(6);
-        let first_nine = list.push_back(9);
-        list.push_back(12);
+        let ten = list.push_back(10);
+        let twenty = list.push_back(20);
+        let thirty = list.push_front(30);
 
-        list.remove(six);
+        let triples: Vec<_> = list.enumerate_indexed().collect();
 
-        let _second_nine = list.push_back(9);
+        assert_eq!(triples, [(0, thirty, &30), (1, ten, &10), (2, twenty, &20)]);
 
-        assert_eq!(list.index_of(&9).unwrap(), first_nine);
+        for (_, index, item) in triples {
+            assert_eq!(list.get(index), Some(item));
+        }
     }
 
     #[test]
-    fn pop_front() {
-        let mut list = IndexList::new();
+    fn remove_ref() {
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4]);
 
-        list.push_back(5);
-        list.push_back(10);
-        list.push_back(15);
+        let indexes: Vec<_> = list
+            .enumerate_indexed()
+            .filter(|&(position, _, _)| position % 2 == 0)
+            .map(|(_, index, _)| index)
+            .collect();
 
-        assert_eq!(list.pop_front().unwrap(), 5);
-        assert_eq!(list.pop_front().unwrap(), 10);
-        assert_eq!(list.pop_front().unwrap(), 15);
+        for index in &indexes {
+            assert!(list.remove_ref(index).is_some());
+        }
 
-        assert_eq!(
-            list,
-            IndexList {
-                contents: vec![
-                    Entry::Free { next_free: None },
-                    Entry::Free { next_free: Some(0) },
-                    Entry::Free { next_free: Some(1) },
-                ],
-                generation: 3,
-                next_free: Some(2),
-                head: None,
-                tail: None,
-            }
-        );
+        assert_eq!(list, [2, 4]);
+        assert_eq!(list.remove_ref(&indexes[0]), None);
     }
 
     #[test]
-    fn push_and_pop() {
+    fn index_or_dangling() {
         let mut list = IndexList::new();
 
-        list.push_back(5);
-        list.push_back(10);
-        list.push_back(15);
+        let head = list.head_index_or_dangling();
+        let tail = list.tail_index_or_dangling();
 
-        assert_eq!(list.pop_front().unwrap(), 5);
-        assert_eq!(list.pop_front().unwrap(), 10);
-        assert_eq!(list.pop_front().unwrap(), 15);
+        assert_eq!(head, Index::dangling());
+        assert_eq!(tail, Index::dangling());
 
-        list.push_back(5);
-        list.push_back(10);
-        list.push_back(15);
+        // it stays dangling no matter how big the list gets
+        for i in 0..10 {
+            list.push_back(i);
+        }
 
-        assert_eq!(list.pop_front().unwrap(), 5);
-        assert_eq!(list.pop_front().unwrap(), 10);
-        assert_eq!(list.pop_front().unwrap(), 15);
+        assert_eq!(list.get(head), None);
+        assert_eq!(list.get_checked(tail), Err(IndexError::OutOfBounds));
+        assert_eq!(list.remove(head), None);
 
-        assert_eq!(
-            list,
-            IndexList {
-                contents: vec![
-                    Entry::Free { next_free: Some(1) },
-                    Entry::Free { next_free: Some(2) },
-                    Entry::Free { next_free: None },
-                ],
-                generation: 6,
-                next_free: Some(0),
-                head: None,
-                tail: None,
-            }
-        );
+        assert_eq!(list[list.head_index_or_dangling()], 0);
+        assert_eq!(list[list.tail_index_or_dangling()], 9);
     }
 
     #[test]
-    fn push_front_next_free() {
-        let mut list = IndexList::new();
+    fn count_distinct() {
+        let mut list = IndexList::from_slice(&[1, 2, 2, 3, 3, 3]);
 
-        list.push_front(0);
-        list.push_front(73);
-        list.pop_front();
+        assert_eq!(list.count_distinct(), 3);
 
-        list.push_front(1);
-        list.push_front(2);
+        let one = list.head_index().unwrap();
+        list.remove(one);
 
-        assert_eq!(
-            list,
-            IndexList {
-                contents: vec![
-                    Entry::Occupied(OccupiedEntry {
-                        item: 0,
-                        next: None,
-                        prev: Some(1),
-                        generation: 0
-                    }),
-                    Entry::Occupied(OccupiedEntry {
-                        item: 1,
-                        next: Some(0),
-                        prev: Some(2),
-                        generation: 1
-                    }),
-                    Entry::Occupied(OccupiedEntry {
-                        item: 2,
-                        next: Some(1),
-                        prev: None,
-                        generation: 1
-                    })
-                ],
-                generation: 1,
-                next_free: None,
-                head: Some(2),
-                tail: Some(0),
-            }
-        );
+        assert_eq!(list.count_distinct(), 2);
+        assert_eq!(IndexList::<i32>::new().count_distinct(), 0);
     }
 
     #[test]
-    fn insert_before() {
-        let mut list = IndexList::new();
+    fn map_range() {
+        let mut list = IndexList::from_slice(&[1, 2, 3, 4, 5]);
 
-        let index = list.push_front(2);
-        list.insert_before(index, 0);
+        list.map_range(1, 3, |x| *x += 100);
 
-        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 2]);
-        assert_eq!(*list.get(list.prev_index(index).unwrap()).unwrap(), 0);
+        assert_eq!(list, [1, 102, 103, 4, 5]);
 
-        list.insert_before(index, 1);
+        // out of range bits are ignored
+        list.map_range(4, 10, |x| *x = 0);
+        list.map_range(3, 1, |x| *x = 0);
 
-        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 1, 2]);
-        assert_eq!(*list.get(list.prev_index(index).unwrap()).unwrap(), 1);
+        assert_eq!(list, [1, 102, 103, 4, 0]);
+
+        // it follows the links, so moved items are mapped where they now sit
+        let one = list.head_index().unwrap();
+        let zero = list.tail_index().unwrap();
+
+        list.move_after(one, zero);
+        list.map_range(3, 5, |x| *x = -*x);
+
+        assert_eq!(list, [102, 103, 4, 0, -1]);
     }
 
     #[test]
-    fn insert_after() {
-        let mut list = IndexList::new();
+    fn split_first_and_last() {
+        let mut list = IndexList::from_slice(&[1, 2, 3]);
 
-        let index = list.push_front(0);
-        list.insert_after(index, 2);
+        let (first, rest) = list.split_first().unwrap();
 
-        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 2]);
-        assert_eq!(*list.get(list.next_index(index).unwrap()).unwrap(), 2);
+        assert_eq!(first, &1);
+        assert_eq!(rest.collect::<Vec<_>>(), [&2, &3]);
 
-        list.insert_after(index, 1);
+        let (last, rest) = list.split_last().unwrap();
 
-        assert_eq!(list.iter().copied().collect::<Vec<usize>>(), vec![0, 1, 2]);
-        assert_eq!(*list.get(list.next_index(index).unwrap()).unwrap(), 1);
+        assert_eq!(last, &3);
+        assert_eq!(rest.collect::<Vec<_>>(), [&1, &2]);
+
+        list.pop_front();
+        list.pop_front();
+
+        let (first, rest) = list.split_first().unwrap();
+
+        assert_eq!(first, &3);
+        assert_eq!(rest.count(), 0);
+        assert_eq!(
+            list.split_last().map(|(last, rest)| (last, rest.count())),
+            Some((&3, 0))
+        );
+
+        list.pop_front();
+
+        assert!(list.split_first().is_none());
+        assert!(list.split_last().is_none());
+    }
+
+    #[test]
+    fn to_array() {
+        let mut list = IndexList::from_slice(&[1, 2, 3]);
+
+        assert_eq!(list.to_array::<3>(), Some([1, 2, 3]));
+        assert_eq!(list.to_array::<4>(), None);
+        assert_eq!(list.to_array::<0>(), Some([]));
+
+        list.pop_front();
+
+        assert_eq!(list.to_array::<3>(), None);
+        assert_eq!(list.to_array::<2>(), Some([2, 3]));
     }
 }